@@ -1,5 +1,5 @@
 // Clean WebAssembly bindings using pure serde for zero-duplication
-use morse_core::{audio, interpret, timing, types::*};
+use morse_core::{audio, interpret, music, timing, types::*};
 use wasm_bindgen::prelude::*;
 
 // Console logging for debugging
@@ -23,6 +23,16 @@ pub struct MorseConfig {
     pub word_gap_multiplier: f32,
     pub humanization_factor: f32,
     pub random_seed: u32,
+    /// Farnsworth character speed; `0` disables Farnsworth. See
+    /// [`MorseTimingParams::farnsworth_wpm`].
+    pub farnsworth_wpm: i32,
+    /// Which built-in character set to encode against when no custom
+    /// prosigns force a dictionary build. See
+    /// [`MorseTimingParams::charset`].
+    pub charset: morse_core::patterns::Charset,
+    /// How to handle characters missing from the active dictionary. See
+    /// [`MorseTimingParams::unknown_char_policy`].
+    pub unknown_char_policy: morse_core::patterns::UnknownCharPolicy,
 
     // Audio parameters
     pub sample_rate: i32,
@@ -30,6 +40,17 @@ pub struct MorseConfig {
     pub low_pass_cutoff: f32,
     pub high_pass_cutoff: f32,
     pub audio_mode: MorseAudioMode,
+    /// Container format the generated samples are encoded into. See
+    /// [`MorseAudioParams::format`].
+    pub format: MorseAudioFormat,
+    /// Peaking/bell EQ center frequency in Hz. See
+    /// [`MorseAudioParams::bell_freq`].
+    pub bell_freq: f32,
+    /// Peaking/bell EQ quality factor. See [`MorseAudioParams::bell_q`].
+    pub bell_q: f32,
+    /// Peaking/bell EQ gain in dB (0 = bypassed). See
+    /// [`MorseAudioParams::bell_gain_db`].
+    pub bell_gain_db: f32,
 
     // Radio mode parameters
     pub freq_hz: f32,
@@ -44,6 +65,15 @@ pub struct MorseConfig {
     pub solenoid_response: f32,
     pub room_tone_level: f32,
     pub reverb_amount: f32,
+
+    // Prosign parameters
+    /// Whether `<NAME>` angle-bracket tokens (e.g. `<AR>`, `<SOS>`) are parsed
+    /// as run-together prosigns.
+    pub enable_prosign_markup: bool,
+    /// Additional named prosigns, keyed by name (e.g. `"AR"`) with the pattern
+    /// given as a dot/dash string (e.g. `"...-.-"`). Merged over the built-in
+    /// ITU prosign table; entries with an invalid pattern are ignored.
+    pub custom_prosigns: std::collections::HashMap<String, String>,
 }
 
 impl Default for MorseConfig {
@@ -57,6 +87,9 @@ impl Default for MorseConfig {
             word_gap_multiplier: timing_defaults.word_gap_multiplier,
             humanization_factor: timing_defaults.humanization_factor,
             random_seed: timing_defaults.random_seed,
+            farnsworth_wpm: timing_defaults.farnsworth_wpm,
+            charset: timing_defaults.charset,
+            unknown_char_policy: timing_defaults.unknown_char_policy,
 
             // Audio defaults
             sample_rate: audio_defaults.sample_rate,
@@ -64,6 +97,10 @@ impl Default for MorseConfig {
             low_pass_cutoff: audio_defaults.low_pass_cutoff,
             high_pass_cutoff: audio_defaults.high_pass_cutoff,
             audio_mode: audio_defaults.audio_mode,
+            format: audio_defaults.format,
+            bell_freq: audio_defaults.bell_freq,
+            bell_q: audio_defaults.bell_q,
+            bell_gain_db: audio_defaults.bell_gain_db,
 
             // Radio defaults
             freq_hz: audio_defaults.radio_params.freq_hz,
@@ -78,17 +115,53 @@ impl Default for MorseConfig {
             solenoid_response: audio_defaults.telegraph_params.solenoid_response,
             room_tone_level: audio_defaults.telegraph_params.room_tone_level,
             reverb_amount: audio_defaults.telegraph_params.reverb_amount,
+
+            // Prosign defaults
+            enable_prosign_markup: timing_defaults.enable_prosign_markup,
+            custom_prosigns: std::collections::HashMap::new(),
         }
     }
 }
 
+/// Parse a dot/dash string (e.g. `"...-.-"`) into pattern elements, as used
+/// by [`MorseConfig::custom_prosigns`]. Returns `None` on any character other
+/// than `.` or `-`.
+fn parse_dot_dash_pattern(pattern: &str) -> Option<Vec<MorseElementType>> {
+    pattern
+        .chars()
+        .map(|c| match c {
+            '.' => Some(MorseElementType::Dot),
+            '-' => Some(MorseElementType::Dash),
+            _ => None,
+        })
+        .collect()
+}
+
 impl MorseConfig {
     fn to_timing_params(&self) -> MorseTimingParams {
+        let dictionary = if self.custom_prosigns.is_empty() {
+            None
+        } else {
+            let mut dict = morse_core::patterns::MorseDictionary::itu();
+            for (name, pattern) in &self.custom_prosigns {
+                if let Some(elements) = parse_dot_dash_pattern(pattern) {
+                    dict.insert_prosign(name, &elements);
+                }
+            }
+            Some(dict)
+        };
+
         MorseTimingParams {
             wpm: self.wpm,
             word_gap_multiplier: self.word_gap_multiplier,
             humanization_factor: self.humanization_factor,
             random_seed: self.random_seed,
+            farnsworth_wpm: self.farnsworth_wpm,
+            charset: self.charset,
+            unknown_char_policy: self.unknown_char_policy,
+            enable_prosign_markup: self.enable_prosign_markup,
+            dictionary,
+            ..Default::default()
         }
     }
 
@@ -99,6 +172,10 @@ impl MorseConfig {
             low_pass_cutoff: self.low_pass_cutoff,
             high_pass_cutoff: self.high_pass_cutoff,
             audio_mode: self.audio_mode,
+            format: self.format,
+            bell_freq: self.bell_freq,
+            bell_q: self.bell_q,
+            bell_gain_db: self.bell_gain_db,
             radio_params: MorseRadioParams {
                 freq_hz: self.freq_hz,
                 waveform_type: self.waveform_type,
@@ -113,6 +190,7 @@ impl MorseConfig {
                 room_tone_level: self.room_tone_level,
                 reverb_amount: self.reverb_amount,
             },
+            ..Default::default()
         }
     }
 }
@@ -154,13 +232,17 @@ pub fn morse_audio_json(text: &str, config_json: &str) -> Result<String, JsValue
 
     // Generate audio
     let audio_params = config.to_audio_params();
-    let audio_data = audio::morse_audio(&timing_elements, &audio_params)
+    let samples = audio::morse_audio(&timing_elements, &audio_params)
+        .map_err(|e| JsValue::from_str(&e))?;
+    let audio_data = audio::encode_audio(&samples, audio_params.sample_rate, audio_params.format)
         .map_err(|e| JsValue::from_str(&e))?;
 
     // Calculate total duration
     let total_duration: f32 = timing_elements.iter().map(|e| e.duration_seconds).sum();
 
-    // Return structured result as JSON
+    // Return structured result as JSON; `audioData` is a file-ready byte
+    // buffer (WAV/FLAC/Ogg per `format`), suitable to write to disk or hand
+    // to a browser `Blob` as-is.
     let result = serde_json::json!({
         "audioData": audio_data,
         "sampleRate": audio_params.sample_rate,
@@ -192,6 +274,224 @@ pub fn morse_interpret_json(signals_json: &str, config_json: &str) -> Result<Str
         .map_err(|e| JsValue::from_str(&format!("JSON serialization error: {}", e)))
 }
 
+/// Interpret morse signals from JSON, returning the top `n` decodings with
+/// normalized confidences instead of a single best guess
+#[wasm_bindgen]
+pub fn morse_interpret_n_best_json(
+    signals_json: &str,
+    config_json: &str,
+    n: usize,
+) -> Result<String, JsValue> {
+    let signals: Vec<MorseSignal> = serde_json::from_str(signals_json)
+        .map_err(|e| JsValue::from_str(&format!("Invalid signals JSON: {}", e)))?;
+
+    let params: MorseInterpretParams = if config_json.trim().is_empty() {
+        MorseInterpretParams::default()
+    } else {
+        serde_json::from_str(config_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid config JSON: {}", e)))?
+    };
+
+    let result = interpret::morse_interpret_n_best(&signals, &params, n)
+        .map_err(|e| JsValue::from_str(&e))?;
+
+    serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("JSON serialization error: {}", e)))
+}
+
+/// Configuration for [`morse_decode_audio_json`]: demodulation parameters
+/// (how to recover keying from the raw PCM) plus the interpreter's own
+/// config (how to turn that keying into text).
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AudioDecodeConfig {
+    /// Sample rate of `samples_json`, in Hz.
+    pub sample_rate: u32,
+    #[serde(flatten)]
+    pub demod: DemodParams,
+    #[serde(flatten)]
+    pub interpret: MorseInterpretParams,
+    /// Return the demodulated `MorseSignal`s as JSON instead of decoding
+    /// them to text.
+    pub signals_only: bool,
+}
+
+impl Default for AudioDecodeConfig {
+    fn default() -> Self {
+        Self {
+            sample_rate: 44100,
+            demod: DemodParams::default(),
+            interpret: MorseInterpretParams::default(),
+            signals_only: false,
+        }
+    }
+}
+
+/// Decode a raw PCM recording straight to Morse text (or, with
+/// `signalsOnly`, the demodulated `MorseSignal` runs).
+///
+/// `samples_json` is a JSON array of mono audio samples; `config_json`
+/// configures both the demodulator ([`DemodParams`], e.g. `freqHz`,
+/// `thresholdRatio`, `fixedThreshold`) and the interpreter
+/// ([`MorseInterpretParams`], e.g. `autoTiming`). Demodulation and
+/// interpretation are otherwise the same `audio::demodulate_to_signals` +
+/// `interpret::morse_interpret` pipeline used elsewhere in this crate, so a
+/// caller with pre-segmented signals should keep using
+/// [`morse_interpret_json`] directly.
+#[wasm_bindgen]
+pub fn morse_decode_audio_json(samples_json: &str, config_json: &str) -> Result<String, JsValue> {
+    let samples: Vec<f32> = serde_json::from_str(samples_json)
+        .map_err(|e| JsValue::from_str(&format!("Invalid samples JSON: {}", e)))?;
+
+    let config: AudioDecodeConfig = if config_json.trim().is_empty() {
+        AudioDecodeConfig::default()
+    } else {
+        serde_json::from_str(config_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid config JSON: {}", e)))?
+    };
+
+    let signals = audio::demodulate_to_signals(&samples, config.sample_rate, &config.demod);
+
+    if config.signals_only {
+        return serde_json::to_string(&signals)
+            .map_err(|e| JsValue::from_str(&format!("JSON serialization error: {}", e)));
+    }
+
+    let result = interpret::morse_interpret(&signals, &config.interpret)
+        .map_err(|e| JsValue::from_str(&e))?;
+
+    serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("JSON serialization error: {}", e)))
+}
+
+/// Configuration for [`morse_music_json`]: timing parameters (how `text`
+/// becomes dot/dash/gap elements) plus the note-rendering parameters (how
+/// those elements become pitched, timed notes).
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MorseMusicConfig {
+    #[serde(flatten)]
+    pub timing: MorseTimingParams,
+    #[serde(flatten)]
+    pub music: MorseMusicParams,
+    /// Also render the note list as a Standard MIDI File, returned as
+    /// `midiData` (a raw byte array).
+    pub include_midi: bool,
+}
+
+impl Default for MorseMusicConfig {
+    fn default() -> Self {
+        Self {
+            timing: MorseTimingParams::default(),
+            music: MorseMusicParams::default(),
+            include_midi: false,
+        }
+    }
+}
+
+/// Render text as musical note events (dot -> short note, dash -> long note,
+/// gap -> rest) driven by a configurable scale/root, rather than as legible
+/// Morse.
+///
+/// Returns `{ notes: NoteEvent[], midiData?: number[] }` as JSON; set
+/// `includeMidi` to also render a Standard MIDI File from the same notes.
+#[wasm_bindgen]
+pub fn morse_music_json(text: &str, config_json: &str) -> Result<String, JsValue> {
+    let config: MorseMusicConfig = if config_json.trim().is_empty() {
+        MorseMusicConfig::default()
+    } else {
+        serde_json::from_str(config_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid config JSON: {}", e)))?
+    };
+
+    let elements =
+        timing::morse_timing(text, &config.timing).map_err(|e| JsValue::from_str(&e))?;
+    let notes = music::generate_notes(&elements, &config.music);
+
+    let mut result = serde_json::json!({ "notes": notes });
+    if config.include_midi {
+        let midi = music::generate_midi(&notes, &config.music).map_err(|e| JsValue::from_str(&e))?;
+        result["midiData"] = serde_json::json!(midi);
+    }
+
+    serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("JSON serialization error: {}", e)))
+}
+
+/// Interpret alternating ON/OFF signals into text via 1-D k-means duration
+/// clustering, as an alternative to the n-gram-correction pipeline behind
+/// [`morse_interpret_json`].
+#[wasm_bindgen]
+pub fn morse_interpret_kmeans_json(
+    signals_json: &str,
+    config_json: &str,
+) -> Result<String, JsValue> {
+    let signals: Vec<MorseSignal> = serde_json::from_str(signals_json)
+        .map_err(|e| JsValue::from_str(&format!("Invalid signals JSON: {}", e)))?;
+
+    let params: KMeansInterpretParams = if config_json.trim().is_empty() {
+        KMeansInterpretParams::default()
+    } else {
+        serde_json::from_str(config_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid config JSON: {}", e)))?
+    };
+
+    let text = timing::interpret_morse_signals(&signals, &params)
+        .map_err(|e| JsValue::from_str(&e))?;
+
+    serde_json::to_string(&text)
+        .map_err(|e| JsValue::from_str(&format!("JSON serialization error: {}", e)))
+}
+
+/// Render Morse timing elements as a Standard MIDI File (type 0), every
+/// dot/dash sounded at the same pitch. For melodic rendering driven by a
+/// scale/root instead, see [`morse_music_json`].
+///
+/// Returns `{ midiData: number[] }` as JSON.
+#[wasm_bindgen]
+pub fn morse_midi_json(elements_json: &str, config_json: &str) -> Result<String, JsValue> {
+    let elements: Vec<MorseElement> = serde_json::from_str(elements_json)
+        .map_err(|e| JsValue::from_str(&format!("Invalid elements JSON: {}", e)))?;
+
+    let params: MorseMidiParams = if config_json.trim().is_empty() {
+        MorseMidiParams::default()
+    } else {
+        serde_json::from_str(config_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid config JSON: {}", e)))?
+    };
+
+    let midi_data = timing::morse_midi(&elements, &params).map_err(|e| JsValue::from_str(&e))?;
+
+    let result = serde_json::json!({ "midiData": midi_data });
+    serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("JSON serialization error: {}", e)))
+}
+
+/// Detect ON/OFF keying from a raw mono audio buffer (RMS envelope plus
+/// autocorrelation tone confirmation), returning the same [`MorseElement`]
+/// shape produced by [`morse_timing_json`].
+#[wasm_bindgen]
+pub fn morse_detect_json(
+    samples_json: &str,
+    sample_rate: i32,
+    config_json: &str,
+) -> Result<String, JsValue> {
+    let samples: Vec<f32> = serde_json::from_str(samples_json)
+        .map_err(|e| JsValue::from_str(&format!("Invalid samples JSON: {}", e)))?;
+
+    let params: DetectParams = if config_json.trim().is_empty() {
+        DetectParams::default()
+    } else {
+        serde_json::from_str(config_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid config JSON: {}", e)))?
+    };
+
+    let elements = audio::detect_morse_signals(&samples, sample_rate, &params);
+
+    serde_json::to_string(&elements)
+        .map_err(|e| JsValue::from_str(&format!("JSON serialization error: {}", e)))
+}
+
 // Alternative API using wasm-bindgen's direct serde integration (experimental)
 
 /// Generate morse timing using JsValue (direct serde integration)
@@ -226,7 +526,9 @@ pub fn morse_audio_direct(text: &str, config: &JsValue) -> Result<JsValue, JsVal
         .map_err(|e| JsValue::from_str(&e))?;
 
     let audio_params = config.to_audio_params();
-    let audio_data = audio::morse_audio(&timing_elements, &audio_params)
+    let samples = audio::morse_audio(&timing_elements, &audio_params)
+        .map_err(|e| JsValue::from_str(&e))?;
+    let audio_data = audio::encode_audio(&samples, audio_params.sample_rate, audio_params.format)
         .map_err(|e| JsValue::from_str(&e))?;
 
     let total_duration: f32 = timing_elements.iter().map(|e| e.duration_seconds).sum();
@@ -240,4 +542,30 @@ pub fn morse_audio_direct(text: &str, config: &JsValue) -> Result<JsValue, JsVal
 
     serde_wasm_bindgen::to_value(&result)
         .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every field a JSON caller can set on `MorseConfig` must survive the
+    /// round trip into the underlying `MorseAudioParams`/`MorseTimingParams`
+    /// — `to_audio_params`/`to_timing_params` falling back to
+    /// `..Default::default()` for a field silently no-ops it for every WASM
+    /// caller.
+    #[test]
+    fn to_audio_params_threads_bell_eq() {
+        let config = MorseConfig {
+            bell_freq: 1200.0,
+            bell_q: 2.5,
+            bell_gain_db: 6.0,
+            ..MorseConfig::default()
+        };
+
+        let audio_params = config.to_audio_params();
+
+        assert_eq!(audio_params.bell_freq, 1200.0);
+        assert_eq!(audio_params.bell_q, 2.5);
+        assert_eq!(audio_params.bell_gain_db, 6.0);
+    }
 }
\ No newline at end of file
@@ -1,63 +1,115 @@
+use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::path::Path;
 
+/// Most common English trigram ("the") and its raw frequency, used as the
+/// cost-scale reference for every order so costs stay comparable across the
+/// back-off chain.
+const REFERENCE_FREQ: f64 = 77534223.0;
+
+/// Convert a raw frequency into a negative-log-ish cost on the same log
+/// scale the original trigram table used (higher frequency = lower cost).
+fn freq_to_cost(freq: u64) -> f32 {
+    if freq == 0 {
+        return 8.0;
+    }
+    let normalized = (REFERENCE_FREQ / freq as f64).ln();
+    (normalized * 0.5).min(4.0) as f32
+}
+
+/// Write a `&[(&str, f32)]` data array to `dest`, sorted by descending
+/// frequency and capped at `max_entries`.
+fn write_ngram_table(
+    dest: &Path,
+    label: &str,
+    mut entries: Vec<(String, u64)>,
+    max_entries: usize,
+) -> std::io::Result<()> {
+    entries.sort_by(|a, b| b.1.cmp(&a.1));
+    entries.truncate(max_entries);
+
+    let mut output = String::new();
+    output.push_str(&format!(
+        "// Auto-generated {} data from english_3grams.csv\n",
+        label
+    ));
+    output.push_str("// DO NOT EDIT - regenerated at build time\n");
+    output.push_str("&[\n");
+
+    for (gram, freq) in entries {
+        output.push_str(&format!(
+            "    (\"{}\", {:.3}),\n",
+            gram,
+            freq_to_cost(freq)
+        ));
+    }
+
+    output.push_str("]\n");
+    fs::write(dest, output)
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let out_dir = env::var("OUT_DIR")?;
-    let dest_path = Path::new(&out_dir).join("trigrams.rs");
 
-    // Read the CSV file
+    // Top-N caps per order - lower orders have a much smaller alphabet of
+    // distinct grams, so their caps are correspondingly smaller.
+    const MAX_TRIGRAMS: usize = 2000;
+    const MAX_BIGRAMS: usize = 700;
+    const MAX_UNIGRAMS: usize = 40;
+
+    // Read the CSV file (trigram, frequency) pairs
     let csv_content = fs::read_to_string("english_3grams.csv")?;
 
-    // Parse CSV and convert to Rust code
-    let mut trigram_data = Vec::new();
-    let mut line_count = 0;
-    const MAX_TRIGRAMS: usize = 2000; // Only use top 2000 for performance
+    let mut trigram_freq: Vec<(String, u64)> = Vec::new();
+    // Lower orders aren't present in the source data, so they're marginalized
+    // from the trigram counts: every (c0, c1, c2) trigram implies a
+    // (c1 -> c2) bigram and an unconditional c2 unigram occurred alongside it.
+    let mut bigram_freq: HashMap<String, u64> = HashMap::new();
+    let mut unigram_freq: HashMap<String, u64> = HashMap::new();
 
     for line in csv_content.lines().skip(1) {
         // Skip header
-        if line_count >= MAX_TRIGRAMS {
-            break;
+        let parts: Vec<&str> = line.split(',').collect();
+        if parts.len() != 2 {
+            continue;
         }
 
-        let parts: Vec<&str> = line.split(',').collect();
-        if parts.len() == 2 {
-            let trigram = parts[0];
-            let freq: u64 = parts[1].parse().unwrap_or(0);
-
-            // Only include trigrams that are exactly 3 characters
-            if trigram.len() == 3 && freq > 0 {
-                // Convert frequency to cost (higher frequency = lower cost)
-                // Use log scale to compress the range
-                let cost = if freq > 0 {
-                    // Scale: most common trigram "the" (77M) gets cost ~0.5
-                    // Less common trigrams get higher costs up to ~4.0
-                    let normalized = (77534223.0 / freq as f64).ln();
-                    (normalized * 0.5).min(4.0) as f32
-                } else {
-                    8.0
-                };
-
-                trigram_data.push((trigram.to_uppercase(), cost));
-                line_count += 1;
-            }
+        let trigram = parts[0];
+        let freq: u64 = parts[1].parse().unwrap_or(0);
+        if trigram.len() != 3 || freq == 0 {
+            continue;
         }
-    }
 
-    // Generate Rust code - just the data array for inclusion
-    let mut output = String::new();
-    output.push_str("// Auto-generated trigram data from english_3grams.csv\n");
-    output.push_str("// DO NOT EDIT - regenerated at build time\n");
-    output.push_str("&[\n");
+        let upper = trigram.to_uppercase();
+        let bytes = upper.as_bytes();
 
-    for (trigram, cost) in trigram_data {
-        output.push_str(&format!("    (\"{}\", {:.3}),\n", trigram, cost));
-    }
+        *bigram_freq.entry(upper[1..3].to_string()).or_insert(0) += freq;
+        *unigram_freq
+            .entry((bytes[2] as char).to_string())
+            .or_insert(0) += freq;
 
-    output.push_str("]\n");
+        trigram_freq.push((upper, freq));
+    }
 
-    // Write the generated code
-    fs::write(&dest_path, output)?;
+    write_ngram_table(
+        &Path::new(&out_dir).join("unigrams.rs"),
+        "unigram",
+        unigram_freq.into_iter().collect(),
+        MAX_UNIGRAMS,
+    )?;
+    write_ngram_table(
+        &Path::new(&out_dir).join("bigrams.rs"),
+        "bigram",
+        bigram_freq.into_iter().collect(),
+        MAX_BIGRAMS,
+    )?;
+    write_ngram_table(
+        &Path::new(&out_dir).join("trigrams.rs"),
+        "trigram",
+        trigram_freq,
+        MAX_TRIGRAMS,
+    )?;
 
     println!("cargo:rerun-if-changed=english_3grams.csv");
     println!("cargo:rerun-if-changed=build.rs");
@@ -1,5 +1,6 @@
 use crate::types::{
-    MorseAudioMode, MorseAudioParams, MorseElement, MorseElementType, MorseWaveformType,
+    DemodParams, DetectParams, MorseAudioFormat, MorseAudioMode, MorseAudioParams, MorseElement,
+    MorseElementType, MorseSignal, MorseWaveformType,
 };
 use std::f32::consts::PI;
 
@@ -94,6 +95,43 @@ impl BiquadFilter {
         filter
     }
 
+    fn new_peaking(center_freq: f32, q: f32, gain_db: f32, sample_rate: f32) -> Self {
+        let mut filter = Self::default();
+
+        // Bypass the no-op case (flat response at 0 dB).
+        if gain_db == 0.0 {
+            filter.a0 = 1.0;
+            return filter;
+        }
+
+        // Clamp to sane ranges; low center frequencies with small Q produce
+        // badly asymmetric responses otherwise.
+        let center_freq = center_freq.clamp(20.0, sample_rate * 0.49);
+        let q = q.clamp(0.1, 10.0);
+
+        // Standard RBJ peaking EQ coefficients.
+        let a = 10.0f32.powf(gain_db / 40.0);
+        let w0 = 2.0 * PI * center_freq / sample_rate;
+        let cos_w0 = w0.cos();
+        let alpha = w0.sin() / (2.0 * q);
+
+        let b0 = 1.0 + alpha * a;
+        let b1 = -2.0 * cos_w0;
+        let b2 = 1.0 - alpha * a;
+        let a0 = 1.0 + alpha / a;
+        let a1 = -2.0 * cos_w0;
+        let a2 = 1.0 - alpha / a;
+
+        // Fit the numerator/denominator into the existing a*/b* layout.
+        filter.a0 = b0 / a0;
+        filter.a1 = b1 / a0;
+        filter.a2 = b2 / a0;
+        filter.b1 = a1 / a0;
+        filter.b2 = a2 / a0;
+
+        filter
+    }
+
     fn process(&mut self, input: f32) -> f32 {
         let output = self.a0 * input + self.a1 * self.x1 + self.a2 * self.x2
             - self.b1 * self.y1
@@ -109,34 +147,73 @@ impl BiquadFilter {
     }
 }
 
-// Waveform generation
-fn generate_waveform(waveform_type: MorseWaveformType, frequency: f32, time: f32) -> f32 {
-    let phase = 2.0 * PI * frequency * time;
+/// PolyBLEP (polynomial band-limited step) residual used to antialias the
+/// discontinuities of geometric waveforms. `t` is the normalized phase in
+/// [0, 1) and `dt` the per-sample phase increment.
+fn poly_blep(t: f32, dt: f32) -> f32 {
+    if t < dt {
+        let x = t / dt;
+        2.0 * x - x * x - 1.0
+    } else if t > 1.0 - dt {
+        let x = (t - 1.0) / dt;
+        x * x + 2.0 * x + 1.0
+    } else {
+        0.0
+    }
+}
 
-    match waveform_type {
-        MorseWaveformType::Sine => phase.sin(),
+/// Stateful phase-accumulating oscillator with band-limited geometric outputs.
+///
+/// The naive `%`-based saw/square/triangle alias badly at high `freq_hz` or low
+/// sample rates; PolyBLEP correction removes the aliased harmonics. Phase is
+/// tracked in [0, 1) and advanced by `dt = freq/sample_rate` each sample.
+struct Oscillator {
+    phase: f32,
+    /// Leaky-integrator state for the band-limited triangle.
+    tri_state: f32,
+}
 
-        MorseWaveformType::Square => {
-            if phase.sin() >= 0.0 {
-                1.0
-            } else {
-                -1.0
-            }
+impl Oscillator {
+    fn new() -> Self {
+        Self {
+            phase: 0.0,
+            tri_state: 0.0,
         }
+    }
 
-        MorseWaveformType::Sawtooth => {
-            let normalized_phase = phase % (2.0 * PI);
-            (normalized_phase / PI) - 1.0
-        }
+    fn tick(&mut self, waveform_type: MorseWaveformType, dt: f32) -> f32 {
+        let t = self.phase;
 
-        MorseWaveformType::Triangle => {
-            let normalized_phase = phase % (2.0 * PI);
-            if normalized_phase <= PI {
-                (2.0 * normalized_phase / PI) - 1.0 // Rising edge: -1 to 1
-            } else {
-                3.0 - (2.0 * normalized_phase / PI) // Falling edge: 1 to -1
+        let output = match waveform_type {
+            MorseWaveformType::Sine => (2.0 * PI * t).sin(),
+
+            MorseWaveformType::Sawtooth => 2.0 * t - 1.0 - poly_blep(t, dt),
+
+            MorseWaveformType::Square => {
+                let mut s = if t < 0.5 { 1.0 } else { -1.0 };
+                s += poly_blep(t, dt);
+                s -= poly_blep((t + 0.5) % 1.0, dt);
+                s
             }
+
+            MorseWaveformType::Triangle => {
+                // Leaky-integrate the band-limited square to get an alias-free
+                // triangle; the leak prevents DC drift from accumulating.
+                let mut square = if t < 0.5 { 1.0 } else { -1.0 };
+                square += poly_blep(t, dt);
+                square -= poly_blep((t + 0.5) % 1.0, dt);
+                self.tri_state += 4.0 * dt * square;
+                self.tri_state *= 0.999;
+                self.tri_state
+            }
+        };
+
+        self.phase += dt;
+        if self.phase >= 1.0 {
+            self.phase -= 1.0;
         }
+
+        output
     }
 }
 
@@ -183,8 +260,19 @@ fn morse_audio_radio(
     let mut lowpass = BiquadFilter::new_lowpass(params.low_pass_cutoff, params.sample_rate as f32);
     let mut highpass =
         BiquadFilter::new_highpass(params.high_pass_cutoff, params.sample_rate as f32);
+    let mut peaking = BiquadFilter::new_peaking(
+        params.bell_freq,
+        params.bell_q,
+        params.bell_gain_db,
+        params.sample_rate as f32,
+    );
     let mut rng = AudioRng::new();
 
+    // Stateful oscillator advanced across tone samples (kept resident so phase
+    // stays continuous and the band-limiting integrator doesn't restart).
+    let mut osc = Oscillator::new();
+    let dt = radio.freq_hz / params.sample_rate as f32;
+
     let mut samples = Vec::new();
 
     for elem in events {
@@ -202,7 +290,8 @@ fn morse_audio_radio(
 
                 // Apply filters
                 let filtered = highpass.process(signal);
-                let output = lowpass.process(filtered);
+                let belled = peaking.process(filtered);
+                let output = lowpass.process(belled);
                 samples.push(output);
             }
         } else {
@@ -217,7 +306,6 @@ fn morse_audio_radio(
             let release_start = elem_samples.saturating_sub(release_samples);
 
             for j in 0..elem_samples {
-                let t = j as f32 / params.sample_rate as f32;
                 let mut envelope = 1.0;
 
                 // Calculate envelope
@@ -227,7 +315,7 @@ fn morse_audio_radio(
                     envelope = (elem_samples - j) as f32 / release_samples as f32;
                 }
 
-                let waveform = generate_waveform(radio.waveform_type, radio.freq_hz, t);
+                let waveform = osc.tick(radio.waveform_type, dt);
                 let mut signal = waveform * clamped_volume * envelope;
 
                 // Add background static if enabled
@@ -237,7 +325,8 @@ fn morse_audio_radio(
 
                 // Apply filters
                 let filtered = highpass.process(signal);
-                let output = lowpass.process(filtered);
+                let belled = peaking.process(filtered);
+                let output = lowpass.process(belled);
                 samples.push(output);
             }
         }
@@ -246,6 +335,104 @@ fn morse_audio_radio(
     Ok(samples)
 }
 
+// === Freeverb-style reverb (Schroeder reverberator) ===
+
+// Classic Freeverb comb/allpass tunings in samples at 44.1 kHz.
+const COMB_TUNINGS: [usize; 8] = [1116, 1188, 1277, 1356, 1422, 1491, 1557, 1617];
+const ALLPASS_TUNINGS: [usize; 4] = [556, 441, 341, 225];
+const COMB_FEEDBACK: f32 = 0.84; // ~room size
+const COMB_DAMP: f32 = 0.2;
+const ALLPASS_FEEDBACK: f32 = 0.5;
+
+// Feedback comb filter with a one-pole damping lowpass in the feedback path.
+struct CombFilter {
+    buffer: Vec<f32>,
+    index: usize,
+    feedback: f32,
+    damp1: f32,
+    damp2: f32,
+    filter_store: f32,
+}
+
+impl CombFilter {
+    fn new(len: usize, feedback: f32, damp: f32) -> Self {
+        Self {
+            buffer: vec![0.0; len.max(1)],
+            index: 0,
+            feedback,
+            damp1: damp,
+            damp2: 1.0 - damp,
+            filter_store: 0.0,
+        }
+    }
+
+    fn process(&mut self, input: f32) -> f32 {
+        let output = self.buffer[self.index];
+        self.filter_store = output * self.damp2 + self.filter_store * self.damp1;
+        self.buffer[self.index] = input + self.filter_store * self.feedback;
+        self.index = (self.index + 1) % self.buffer.len();
+        output
+    }
+}
+
+// Series all-pass filter for diffusion.
+struct AllPassFilter {
+    buffer: Vec<f32>,
+    index: usize,
+    feedback: f32,
+}
+
+impl AllPassFilter {
+    fn new(len: usize, feedback: f32) -> Self {
+        Self {
+            buffer: vec![0.0; len.max(1)],
+            index: 0,
+            feedback,
+        }
+    }
+
+    fn process(&mut self, input: f32) -> f32 {
+        let buffered = self.buffer[self.index];
+        let output = -input + buffered;
+        self.buffer[self.index] = input + buffered * self.feedback;
+        self.index = (self.index + 1) % self.buffer.len();
+        output
+    }
+}
+
+// Eight parallel combs summed into four series all-passes, with delay lengths
+// rescaled to the actual sample rate. Allocated once and run sample-by-sample.
+struct Freeverb {
+    combs: Vec<CombFilter>,
+    allpasses: Vec<AllPassFilter>,
+}
+
+impl Freeverb {
+    fn new(sample_rate: f32) -> Self {
+        let scale = sample_rate / 44100.0;
+        let combs = COMB_TUNINGS
+            .iter()
+            .map(|&t| CombFilter::new((t as f32 * scale) as usize, COMB_FEEDBACK, COMB_DAMP))
+            .collect();
+        let allpasses = ALLPASS_TUNINGS
+            .iter()
+            .map(|&t| AllPassFilter::new((t as f32 * scale) as usize, ALLPASS_FEEDBACK))
+            .collect();
+        Self { combs, allpasses }
+    }
+
+    fn process(&mut self, input: f32) -> f32 {
+        let mut out = 0.0;
+        for comb in &mut self.combs {
+            out += comb.process(input);
+        }
+        for allpass in &mut self.allpasses {
+            out = allpass.process(out);
+        }
+        out
+    }
+}
+
 // Telegraph click generation with mechanical resonance
 fn generate_telegraph_click(
     t: f32,
@@ -296,8 +483,27 @@ fn morse_audio_telegraph(
     let mut lowpass = BiquadFilter::new_lowpass(params.low_pass_cutoff, params.sample_rate as f32);
     let mut highpass =
         BiquadFilter::new_highpass(params.high_pass_cutoff, params.sample_rate as f32);
+    let mut peaking = BiquadFilter::new_peaking(
+        params.bell_freq,
+        params.bell_q,
+        params.bell_gain_db,
+        params.sample_rate as f32,
+    );
     let mut room_tone = RoomToneGenerator::new();
 
+    // Freeverb reverb applied to the telegraph output; the delay lines are
+    // allocated once and run per-sample. `reverb_amount` mixes dry/wet.
+    let reverb_amount = telegraph.reverb_amount.clamp(0.0, 1.0);
+    let mut reverb = Freeverb::new(params.sample_rate as f32);
+    let mix_reverb = |dry: f32, reverb: &mut Freeverb| -> f32 {
+        if reverb_amount <= 0.0 {
+            dry
+        } else {
+            let wet = reverb.process(dry);
+            dry * (1.0 - reverb_amount) + wet * reverb_amount
+        }
+    };
+
     let mut samples = Vec::new();
 
     for elem in events {
@@ -315,8 +521,9 @@ fn morse_audio_telegraph(
 
                 // Apply filters
                 let filtered = highpass.process(signal);
-                let output = lowpass.process(filtered);
-                samples.push(output);
+                let belled = peaking.process(filtered);
+                let output = lowpass.process(belled);
+                samples.push(mix_reverb(output, &mut reverb));
             }
         } else {
             // Generate telegraph click
@@ -339,8 +546,9 @@ fn morse_audio_telegraph(
 
                 // Apply filters
                 let filtered = highpass.process(signal);
-                let output = lowpass.process(filtered);
-                samples.push(output);
+                let belled = peaking.process(filtered);
+                let output = lowpass.process(belled);
+                samples.push(mix_reverb(output, &mut reverb));
             }
         }
     }
@@ -364,6 +572,387 @@ pub fn morse_audio(events: &[MorseElement], params: &MorseAudioParams) -> Result
     }
 }
 
+/// Serialize raw `f32` samples into an encoded audio container.
+///
+/// The default [`MorseAudioFormat::Wav`] writes a self-contained 16-bit PCM
+/// RIFF file with no external dependencies. The compressed formats are gated
+/// behind Cargo features (`flac`, `vorbis`); without them they return an error
+/// so the default export path stays dependency-free.
+pub fn encode_audio(
+    samples: &[f32],
+    sample_rate: i32,
+    format: MorseAudioFormat,
+) -> Result<Vec<u8>, String> {
+    if sample_rate <= 0 {
+        return Err("Invalid sample rate".to_string());
+    }
+
+    match format {
+        MorseAudioFormat::Wav => Ok(encode_wav_pcm16(samples, sample_rate as u32)),
+        MorseAudioFormat::Flac => encode_flac(samples, sample_rate as u32),
+        MorseAudioFormat::OggVorbis => encode_ogg_vorbis(samples, sample_rate as u32),
+    }
+}
+
+/// Write a mono 16-bit PCM WAV (RIFF `fmt `/`data`) into a byte buffer.
+fn encode_wav_pcm16(samples: &[f32], sample_rate: u32) -> Vec<u8> {
+    const BITS_PER_SAMPLE: u16 = 16;
+    const CHANNELS: u16 = 1;
+
+    let block_align = CHANNELS * BITS_PER_SAMPLE / 8;
+    let byte_rate = sample_rate * block_align as u32;
+    let data_len = samples.len() as u32 * block_align as u32;
+
+    let mut bytes = Vec::with_capacity(44 + data_len as usize);
+    // RIFF header
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVE");
+    // fmt chunk
+    bytes.extend_from_slice(b"fmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes()); // PCM fmt chunk size
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // audio format = PCM
+    bytes.extend_from_slice(&CHANNELS.to_le_bytes());
+    bytes.extend_from_slice(&sample_rate.to_le_bytes());
+    bytes.extend_from_slice(&byte_rate.to_le_bytes());
+    bytes.extend_from_slice(&block_align.to_le_bytes());
+    bytes.extend_from_slice(&BITS_PER_SAMPLE.to_le_bytes());
+    // data chunk
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+    for &s in samples {
+        let clamped = s.clamp(-1.0, 1.0);
+        let v = (clamped * i16::MAX as f32).round() as i16;
+        bytes.extend_from_slice(&v.to_le_bytes());
+    }
+
+    bytes
+}
+
+#[cfg(feature = "flac")]
+fn encode_flac(samples: &[f32], sample_rate: u32) -> Result<Vec<u8>, String> {
+    flac_encode::encode_mono_i16(samples, sample_rate)
+        .map_err(|e| format!("FLAC encoding failed: {e}"))
+}
+
+#[cfg(not(feature = "flac"))]
+fn encode_flac(_samples: &[f32], _sample_rate: u32) -> Result<Vec<u8>, String> {
+    Err("FLAC output requires the `flac` feature".to_string())
+}
+
+#[cfg(feature = "vorbis")]
+fn encode_ogg_vorbis(samples: &[f32], sample_rate: u32) -> Result<Vec<u8>, String> {
+    vorbis_encode::encode_mono_f32(samples, sample_rate)
+        .map_err(|e| format!("Ogg Vorbis encoding failed: {e}"))
+}
+
+#[cfg(not(feature = "vorbis"))]
+fn encode_ogg_vorbis(_samples: &[f32], _sample_rate: u32) -> Result<Vec<u8>, String> {
+    Err("Ogg Vorbis output requires the `vorbis` feature".to_string())
+}
+
+/// Single-bin Goertzel tone magnitude over one window of samples
+fn goertzel_magnitude(window: &[f32], sample_rate: u32, freq_hz: f32) -> f32 {
+    let n = window.len();
+    if n == 0 {
+        return 0.0;
+    }
+
+    let k = (n as f32 * freq_hz / sample_rate as f32).round();
+    let omega = 2.0 * PI * k / n as f32;
+    let coeff = 2.0 * omega.cos();
+
+    let mut s0;
+    let mut s1 = 0.0f32;
+    let mut s2 = 0.0f32;
+    for &x in window {
+        s0 = x + coeff * s1 - s2;
+        s2 = s1;
+        s1 = s0;
+    }
+
+    let power = s1 * s1 + s2 * s2 - coeff * s1 * s2;
+    // Return RMS-like magnitude normalized by window length
+    (power.max(0.0)).sqrt() / n as f32
+}
+
+/// Scan a grid of Goertzel bins across `[lo_hz, hi_hz]` and return the
+/// frequency with the greatest total tone energy. Used to auto-detect the
+/// carrier of an off-air recording when the caller doesn't know it.
+pub fn detect_tone_frequency(samples: &[f32], sample_rate: u32, lo_hz: f32, hi_hz: f32) -> f32 {
+    if samples.is_empty() || sample_rate == 0 || hi_hz <= lo_hz {
+        return lo_hz.max(0.0);
+    }
+
+    // ~25 Hz resolution across the band, evaluated on at most the first second
+    // of the buffer so the sweep stays cheap on long recordings.
+    let steps = (((hi_hz - lo_hz) / 25.0).round() as usize).max(1);
+    let probe_len = samples.len().min(sample_rate as usize); // up to ~1 s
+
+    let mut best_freq = lo_hz;
+    let mut best_energy = -1.0f32;
+    for i in 0..=steps {
+        let freq = lo_hz + (hi_hz - lo_hz) * i as f32 / steps as f32;
+        let energy = goertzel_magnitude(&samples[..probe_len], sample_rate, freq);
+        if energy > best_energy {
+            best_energy = energy;
+            best_freq = freq;
+        }
+    }
+    best_freq
+}
+
+/// Run-length encode a per-window keyed/unkeyed state sequence into
+/// `(state, duration_seconds)` runs, shared by [`demodulate_to_signals`] and
+/// [`detect_morse_signals`] so the two front ends (see their docs for why
+/// both exist) don't duplicate this coalescing step.
+fn rle_states(states: &[bool], seconds_per_window: f32) -> Vec<(bool, f32)> {
+    let mut runs = Vec::new();
+    let Some(&first) = states.first() else {
+        return runs;
+    };
+
+    let mut run_state = first;
+    let mut run_len = 1usize;
+    for &s in &states[1..] {
+        if s == run_state {
+            run_len += 1;
+        } else {
+            runs.push((run_state, run_len as f32 * seconds_per_window));
+            run_state = s;
+            run_len = 1;
+        }
+    }
+    runs.push((run_state, run_len as f32 * seconds_per_window));
+
+    runs
+}
+
+/// Demodulate a raw CW recording into ON/OFF [`MorseSignal`] runs.
+///
+/// Narrowband tone-locked front end: a carrier frequency is Goertzel-tracked
+/// (or swept for, if unspecified) and the signal decoded against it. This is
+/// the front end the `interpret` pipeline expects ([`MorseSignal`] is its
+/// native input), and the one to reach for when the recording is a clean
+/// single-tone CW transmission. See [`detect_morse_signals`] for the
+/// broadband alternative and why both are kept.
+///
+/// Runs a narrow Goertzel tone detector at the expected carrier frequency over
+/// short overlapping windows, smooths the magnitude, applies a squelch floor,
+/// and thresholds the envelope against an adaptive level (a fraction of the
+/// running peak). A short holdoff after each edge suppresses ringing so brief
+/// dropouts don't fragment a dit. Contiguous HIGH/LOW runs are emitted with
+/// their measured durations, ready to feed straight into `interpret`.
+///
+/// This is the complete raw-PCM-to-[`MorseSignal`] front end a hardware CW
+/// decoder would use: [`DemodParams::smoothing_window`] defaults to the usual
+/// ~9-point moving-average span over the per-window magnitudes, [`DemodParams::threshold_ratio`] to ~2/3 of
+/// the running peak, and [`DemodParams::squelch`]/[`DemodParams::holdoff_seconds`]
+/// reject noise-floor chatter and edge ringing respectively — see those
+/// fields' docs for the exact knobs. Set [`DemodParams::fixed_threshold`] to
+/// disable the adaptive peak tracking for a known fixed-speed, fixed-level
+/// recording.
+pub fn demodulate_to_signals(
+    samples: &[f32],
+    sample_rate: u32,
+    params: &DemodParams,
+) -> Vec<MorseSignal> {
+    if samples.is_empty() || sample_rate == 0 {
+        return Vec::new();
+    }
+
+    let window_size = params.window_size.max(1);
+    let hop_size = params.hop_size.max(1);
+
+    // A non-positive target frequency means "scan for the carrier". Sweep a
+    // coarse grid of Goertzel bins across the audible CW band and keep the
+    // strongest responder.
+    let target_freq = if params.freq_hz > 0.0 {
+        params.freq_hz
+    } else {
+        detect_tone_frequency(samples, sample_rate, 200.0, 1200.0)
+    };
+
+    // Tone magnitude per window
+    let mut magnitudes = Vec::new();
+    let mut start = 0usize;
+    while start < samples.len() {
+        let end = (start + window_size).min(samples.len());
+        magnitudes.push(goertzel_magnitude(
+            &samples[start..end],
+            sample_rate,
+            target_freq,
+        ));
+        if end == samples.len() {
+            break;
+        }
+        start += hop_size;
+    }
+
+    // Moving-average smoothing over a small window of magnitudes
+    let smooth_span = params.smoothing_window.max(1);
+    let mut smoothed = Vec::with_capacity(magnitudes.len());
+    for i in 0..magnitudes.len() {
+        let lo = i.saturating_sub(smooth_span / 2);
+        let hi = (i + smooth_span / 2 + 1).min(magnitudes.len());
+        let sum: f32 = magnitudes[lo..hi].iter().sum();
+        smoothed.push(sum / (hi - lo) as f32);
+    }
+
+    // Threshold the envelope to HIGH/LOW using an adaptive peak estimate,
+    // with a squelch floor and a post-edge holdoff.
+    let seconds_per_hop = hop_size as f32 / sample_rate as f32;
+    let holdoff_hops = (params.holdoff_seconds / seconds_per_hop).round() as i64;
+
+    let mut peak = 0.0f32;
+    let mut states = Vec::with_capacity(smoothed.len());
+    let mut current = false;
+    let mut last_edge: i64 = i64::MIN / 2;
+
+    for (i, &mag) in smoothed.iter().enumerate() {
+        // Running peak estimate with slow decay so keying level auto-tracks
+        peak = peak.max(mag);
+        peak *= 0.999;
+
+        let threshold = match params.fixed_threshold {
+            Some(fixed) => fixed,
+            None => (peak * params.threshold_ratio).max(params.squelch),
+        };
+        let want_on = mag >= threshold;
+
+        if want_on != current && (i as i64 - last_edge) >= holdoff_hops {
+            current = want_on;
+            last_edge = i as i64;
+        }
+        states.push(current);
+    }
+
+    // Run-length encode the per-window states into timed signals.
+    rle_states(&states, seconds_per_hop)
+        .into_iter()
+        .map(|(on, seconds)| MorseSignal { on, seconds })
+        .collect()
+}
+
+/// Normalized autocorrelation peak of `window` over the lag band surrounding
+/// `freq_hz`. Returns a value in `[0, 1]`; a clean sinusoid near the expected
+/// pitch scores close to 1.0, while broadband noise scores low.
+fn tone_autocorrelation(window: &[f32], sample_rate: i32, freq_hz: f32) -> f32 {
+    let n = window.len();
+    if n < 2 || sample_rate <= 0 || freq_hz <= 0.0 {
+        return 0.0;
+    }
+
+    let energy: f32 = window.iter().map(|&x| x * x).sum();
+    if energy <= f32::EPSILON {
+        return 0.0;
+    }
+
+    // Search a ±10% band of lags around the expected period.
+    let period = sample_rate as f32 / freq_hz;
+    let lo = (period * 0.9).floor().max(1.0) as usize;
+    let hi = ((period * 1.1).ceil() as usize).min(n - 1);
+
+    let mut best = 0.0f32;
+    for lag in lo..=hi {
+        let mut acc = 0.0f32;
+        let mut head_energy = 0.0f32;
+        let mut tail_energy = 0.0f32;
+        for i in lag..n {
+            acc += window[i] * window[i - lag];
+            head_energy += window[i - lag] * window[i - lag];
+            tail_energy += window[i] * window[i];
+        }
+        // Normalize by the energy of the two overlapping segments, so a
+        // periodic signal scores near 1.0 regardless of how much of the
+        // window the lagged copy overlaps.
+        let denom = (head_energy * tail_energy).sqrt();
+        if denom <= f32::EPSILON {
+            continue;
+        }
+        let norm = acc / denom;
+        if norm > best {
+            best = norm;
+        }
+    }
+    best.clamp(0.0, 1.0)
+}
+
+/// Turn an arbitrary mono f32 buffer into the ON/OFF duration sequence the
+/// interpreter consumes.
+///
+/// Broadband front end: unlike [`demodulate_to_signals`]'s narrow Goertzel
+/// lock, this confirms keying with autocorrelation rather than tracking a
+/// single carrier bin, and emits the shared [`MorseElement`] currency (the
+/// same type `timing`/`music` produce) instead of [`MorseSignal`], so
+/// detected keying can be fed straight into the MIDI/music export path
+/// without a conversion step. Keep reaching for [`demodulate_to_signals`]
+/// for line-in CW reception against a known/swept carrier; reach for this
+/// one when the caller already has [`MorseElement`]-shaped consumers
+/// downstream, or wants autocorrelation's extra rejection of broadband
+/// clicks/hiss over a single-bin tone lock.
+///
+/// A short-window RMS envelope is thresholded against an adaptive floor (a
+/// fraction of the peak envelope), and each keyed window is confirmed to carry
+/// a periodic tone near `freq_hz` via normalized autocorrelation so broadband
+/// clicks and hiss don't register as keying. Contiguous windows of the same
+/// state are coalesced into timed [`MorseElement`]s — keyed runs become `Dot`
+/// placeholders (leaving dit/dah classification to the interpreter) and silent
+/// runs become `Gap`.
+pub fn detect_morse_signals(
+    samples: &[f32],
+    sample_rate: i32,
+    params: &DetectParams,
+) -> Vec<MorseElement> {
+    if samples.is_empty() || sample_rate <= 0 {
+        return Vec::new();
+    }
+
+    let window = ((params.window_ms / 1000.0) * sample_rate as f32).round().max(1.0) as usize;
+
+    // Short-window RMS envelope plus per-window tone confirmation.
+    let mut rms = Vec::new();
+    let mut toned = Vec::new();
+    let mut start = 0usize;
+    while start < samples.len() {
+        let end = (start + window).min(samples.len());
+        let frame = &samples[start..end];
+        let mean_sq: f32 = frame.iter().map(|&x| x * x).sum::<f32>() / frame.len() as f32;
+        rms.push(mean_sq.sqrt());
+        toned.push(
+            tone_autocorrelation(frame, sample_rate, params.freq_hz) >= params.autocorr_threshold,
+        );
+        if end == samples.len() {
+            break;
+        }
+        start += window;
+    }
+
+    // Adaptive keying threshold derived from the peak of the envelope.
+    let peak = rms.iter().cloned().fold(0.0f32, f32::max);
+    let threshold = peak * params.threshold_ratio;
+
+    let states: Vec<bool> = rms
+        .iter()
+        .zip(&toned)
+        .map(|(&level, &has_tone)| level >= threshold && has_tone)
+        .collect();
+
+    // Run-length encode the per-window states into timed elements.
+    let seconds_per_window = window as f32 / sample_rate as f32;
+    rle_states(&states, seconds_per_window)
+        .into_iter()
+        .map(|(keyed, duration_seconds)| MorseElement {
+            element_type: if keyed {
+                MorseElementType::Dot
+            } else {
+                MorseElementType::Gap
+            },
+            duration_seconds,
+        })
+        .collect()
+}
+
 /// Calculate the total number of samples needed for the given timing elements
 pub fn morse_audio_size(
     events: &[MorseElement],
@@ -0,0 +1,296 @@
+//! Post-decode dictionary correction.
+//!
+//! A lexicon trie is searched with a weighted edit-distance DP carried along
+//! each trie edge, so branches whose best achievable cost already exceeds
+//! the threshold are pruned without visiting every dictionary word.
+//! Substitution/insertion/deletion costs are derived from morse pattern
+//! length and pattern edit distance (via [`crate::patterns::get_morse_pattern`])
+//! rather than uniform Levenshtein weights, so corrections favor the kind of
+//! mistakes a beam-search decoder actually makes (dropped/added trailing
+//! elements, single dot/dash confusions) over arbitrary substitutions.
+
+use crate::types::MorseElementType;
+use std::collections::HashMap;
+
+/// Cost, in morse elements, charged per inserted, deleted, or substituted
+/// pattern element.
+const ELEMENT_EDIT_COST: f32 = 1.0;
+/// Cost assigned when a character has no known morse pattern (punctuation
+/// outside the dictionary, etc).
+const UNKNOWN_PATTERN_COST: f32 = 3.0;
+
+/// Parameters controlling the correction pass.
+#[derive(Debug, Clone)]
+pub struct CorrectionParams {
+    /// Weight applied to the total corrected distance when re-scoring:
+    /// `new_cost = corrected_distance * lambda + original_cost`.
+    pub lambda: f32,
+    /// Maximum weighted edit distance (in morse elements) a dictionary word
+    /// may be from a decoded word to be accepted as its correction.
+    pub max_distance: f32,
+}
+
+impl Default for CorrectionParams {
+    fn default() -> Self {
+        Self {
+            lambda: 1.0,
+            max_distance: 3.0,
+        }
+    }
+}
+
+/// Result of correcting a full decoded string against a [`Lexicon`].
+#[derive(Debug, Clone)]
+pub struct Correction {
+    /// The text with each correctable word replaced by its best match.
+    pub text: String,
+    /// `corrected_distance * lambda + original_cost`.
+    pub cost: f32,
+}
+
+/// The morse pattern length for `ch`, in elements; `UNKNOWN_PATTERN_COST`
+/// worth of elements if `ch` has no known pattern.
+fn element_cost(ch: char) -> f32 {
+    crate::patterns::get_morse_pattern(ch.to_ascii_uppercase() as u8)
+        .map(|pattern| pattern.len() as f32 * ELEMENT_EDIT_COST)
+        .unwrap_or(UNKNOWN_PATTERN_COST)
+}
+
+/// Substitution cost between two characters: the element-level edit
+/// distance between their morse patterns (so E<->I, A<->N, D<->B etc. cost
+/// little, since their patterns differ by only one or two elements).
+fn substitution_cost(a: char, b: char) -> f32 {
+    if a == b {
+        return 0.0;
+    }
+    match (
+        crate::patterns::get_morse_pattern(a.to_ascii_uppercase() as u8),
+        crate::patterns::get_morse_pattern(b.to_ascii_uppercase() as u8),
+    ) {
+        (Some(pa), Some(pb)) => pattern_edit_distance(pa, pb) as f32 * ELEMENT_EDIT_COST,
+        _ => UNKNOWN_PATTERN_COST,
+    }
+}
+
+/// Levenshtein distance between two morse element sequences.
+fn pattern_edit_distance(a: &[MorseElementType], b: &[MorseElementType]) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0usize; b.len() + 1];
+    for (i, &ea) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, &eb) in b.iter().enumerate() {
+            let sub = prev[j] + usize::from(ea != eb);
+            curr[j + 1] = sub.min(prev[j] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+struct WordNode {
+    children: HashMap<char, usize>,
+    terminal: bool,
+}
+
+impl WordNode {
+    fn new() -> Self {
+        Self {
+            children: HashMap::new(),
+            terminal: false,
+        }
+    }
+}
+
+/// A word-list trie used to prune the weighted edit-distance search against
+/// the dictionary: the DP row is extended one trie edge (one candidate
+/// character) at a time, so a branch is abandoned the moment its best
+/// achievable cost exceeds the caller's threshold.
+pub struct Lexicon {
+    nodes: Vec<WordNode>,
+}
+
+impl Lexicon {
+    /// An empty lexicon with no words.
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![WordNode::new()],
+        }
+    }
+
+    /// A lexicon preloaded with common English and ham-radio words.
+    pub fn common_words() -> Self {
+        let mut lexicon = Self::new();
+        for word in COMMON_WORDS {
+            lexicon.insert(word);
+        }
+        lexicon
+    }
+
+    /// Add a word (should already be uppercase, matching decoded text).
+    pub fn insert(&mut self, word: &str) {
+        let mut current = 0usize;
+        for ch in word.chars() {
+            let existing = self.nodes[current].children.get(&ch).copied();
+            current = match existing {
+                Some(idx) => idx,
+                None => {
+                    self.nodes.push(WordNode::new());
+                    let idx = self.nodes.len() - 1;
+                    self.nodes[current].children.insert(ch, idx);
+                    idx
+                }
+            };
+        }
+        self.nodes[current].terminal = true;
+    }
+
+    /// Find the lowest-cost dictionary word within `max_distance` of `word`
+    /// under the morse-weighted edit distance, if any survive the threshold.
+    fn best_match(&self, word: &[char], max_distance: f32) -> Option<(String, f32)> {
+        // Row 0: cost of deleting each decoded prefix to align with the
+        // empty candidate prefix (root of the trie).
+        let mut init_row = Vec::with_capacity(word.len() + 1);
+        init_row.push(0.0);
+        for &ch in word {
+            init_row.push(init_row.last().unwrap() + element_cost(ch));
+        }
+
+        let mut best: Option<(String, f32)> = None;
+        let mut path = String::new();
+        self.search(0, &init_row, word, max_distance, &mut path, &mut best);
+        best
+    }
+
+    fn search(
+        &self,
+        node_idx: usize,
+        row: &[f32],
+        word: &[char],
+        max_distance: f32,
+        path: &mut String,
+        best: &mut Option<(String, f32)>,
+    ) {
+        let node = &self.nodes[node_idx];
+        if node.terminal {
+            let distance = row[word.len()];
+            if distance <= max_distance && best.as_ref().map_or(true, |(_, d)| distance < *d) {
+                *best = Some((path.clone(), distance));
+            }
+        }
+
+        for (&ch, &child_idx) in &node.children {
+            let mut next_row = vec![0.0f32; row.len()];
+            next_row[0] = row[0] + element_cost(ch);
+            for j in 1..row.len() {
+                next_row[j] = (row[j - 1] + substitution_cost(ch, word[j - 1]))
+                    .min(row[j] + element_cost(ch))
+                    .min(next_row[j - 1] + element_cost(word[j - 1]));
+            }
+
+            // Every further extension can only add cost, so if the row's
+            // minimum already exceeds the threshold nothing below survives.
+            let row_min = next_row.iter().copied().fold(f32::INFINITY, f32::min);
+            if row_min <= max_distance {
+                path.push(ch);
+                self.search(child_idx, &next_row, word, max_distance, path, best);
+                path.pop();
+            }
+        }
+    }
+}
+
+impl Default for Lexicon {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Run the post-decode dictionary correction pass over `text`, re-scoring
+/// the whole decoding as `corrected_distance * lambda + original_cost`.
+///
+/// Each whitespace-separated word is corrected independently against
+/// `lexicon`; a word with no match inside `params.max_distance` is left as
+/// decoded and contributes nothing to `corrected_distance`.
+pub fn correct_text(
+    text: &str,
+    original_cost: f32,
+    lexicon: &Lexicon,
+    params: &CorrectionParams,
+) -> Correction {
+    let mut corrected_words = Vec::new();
+    let mut total_distance = 0.0f32;
+
+    for word in text.split(' ') {
+        if word.is_empty() {
+            corrected_words.push(String::new());
+            continue;
+        }
+
+        let chars: Vec<char> = word.chars().collect();
+        match lexicon.best_match(&chars, params.max_distance) {
+            Some((corrected, distance)) => {
+                total_distance += distance;
+                corrected_words.push(corrected);
+            }
+            None => corrected_words.push(word.to_string()),
+        }
+    }
+
+    Correction {
+        text: corrected_words.join(" "),
+        cost: total_distance * params.lambda + original_cost,
+    }
+}
+
+/// Common English and ham-radio words for the default lexicon.
+const COMMON_WORDS: &[&str] = &[
+    "THE", "AND", "YOU", "ARE", "FOR", "NOT", "BUT", "ALL", "CAN", "HAD", "HER", "WAS", "ONE",
+    "OUR", "OUT", "DAY", "GET", "HAS", "HIM", "HOW", "NOW", "OLD", "SEE", "TWO", "WAY", "WHO",
+    "BOY", "DID", "ITS", "LET", "PUT", "SAY", "SHE", "TOO", "USE", "HELLO", "WORLD", "GOOD",
+    "MORNING", "NIGHT", "RADIO", "SIGNAL", "RECEIVED", "MESSAGE", "TRANSMIT", "STATION", "FREQ",
+    "FREQUENCY", "ANTENNA", "POWER", "WEATHER", "THANKS", "PLEASE", "OVER", "ROGER", "QSL", "QTH",
+    "QRM", "QRN", "QSB", "QRZ", "CQ", "SOS", "DE", "TEST", "NAME", "RST", "FINE", "BUSY", "AGAIN",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_match_has_zero_distance() {
+        let lexicon = Lexicon::common_words();
+        let params = CorrectionParams::default();
+        let correction = correct_text("HELLO WORLD", 10.0, &lexicon, &params);
+        assert_eq!(correction.text, "HELLO WORLD");
+        assert!((correction.cost - 10.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_corrects_morse_confusable_error() {
+        // "HELLD" differs from "HELLO" only in its last letter (O = ---,
+        // D = -..), a small morse pattern edit distance well within a
+        // generous threshold.
+        let mut lexicon = Lexicon::new();
+        lexicon.insert("HELLO");
+        let params = CorrectionParams {
+            lambda: 1.0,
+            max_distance: 5.0,
+        };
+
+        let correction = correct_text("HELLD", 2.0, &lexicon, &params);
+        assert_eq!(correction.text, "HELLO");
+        assert!(correction.cost > 2.0); // distance-scaled penalty was added
+    }
+
+    #[test]
+    fn test_leaves_unmatched_word_unchanged() {
+        let lexicon = Lexicon::common_words();
+        let params = CorrectionParams {
+            lambda: 1.0,
+            max_distance: 0.5,
+        };
+        let correction = correct_text("XQZVJK", 1.0, &lexicon, &params);
+        assert_eq!(correction.text, "XQZVJK");
+        assert!((correction.cost - 1.0).abs() < 1e-4);
+    }
+}
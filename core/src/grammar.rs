@@ -0,0 +1,237 @@
+//! Finite-state grammar constraints for structured decode targets (callsigns,
+//! Q-codes, RST reports, and similar fixed-format CW traffic).
+//!
+//! The beam search's language model is built for prose and has little to say
+//! about these formats, so decoding them from the LM alone lets timing noise
+//! propagate into wrong letters unchecked. A [`Grammar`] gives the decoder an
+//! explicit notion of "what comes next is still a valid partial match" that
+//! can bias or filter hypotheses alongside the LM cost.
+//!
+//! Internally a grammar is a small NFA: states are plain indices, and a
+//! hypothesis tracks the *set* of states it could currently be in (a
+//! [`GrammarState`]) rather than a single DFA state, since [`Grammar::from_groups`]
+//! doesn't attempt NFA-to-DFA determinization.
+
+use std::collections::BTreeSet;
+
+/// A set of characters a grammar edge accepts, compared case-insensitively.
+#[derive(Debug, Clone)]
+pub struct CharClass {
+    chars: Vec<char>,
+}
+
+impl CharClass {
+    /// Build a class from a string of member characters, e.g. `"ABCXYZ"`.
+    pub fn new(chars: &str) -> Self {
+        Self {
+            chars: chars.chars().map(|c| c.to_ascii_uppercase()).collect(),
+        }
+    }
+
+    /// Build a class from an inclusive ASCII character range, e.g. `'A'..='Z'`.
+    pub fn range(start: char, end: char) -> Self {
+        Self {
+            chars: (start..=end).map(|c| c.to_ascii_uppercase()).collect(),
+        }
+    }
+
+    /// The union of `self` and `other`'s members.
+    pub fn union(mut self, other: CharClass) -> Self {
+        self.chars.extend(other.chars);
+        self
+    }
+
+    fn contains(&self, ch: char) -> bool {
+        self.chars.contains(&ch.to_ascii_uppercase())
+    }
+}
+
+/// One `{min,max}`-repeated character class in a [`Grammar::from_groups`] spec,
+/// e.g. the `[0-9]{1,1}` in a callsign pattern like `[A-Z0-9]{1,2}[0-9][A-Z]{1,3}`.
+#[derive(Debug, Clone)]
+pub struct GrammarGroup {
+    pub class: CharClass,
+    pub min: usize,
+    pub max: usize,
+}
+
+impl GrammarGroup {
+    pub fn new(class: CharClass, min: usize, max: usize) -> Self {
+        Self { class, min, max }
+    }
+}
+
+/// A finite-state grammar compiled from either a sequence of repeated
+/// character-class groups or an alternation over a closed set of words.
+///
+/// Compiled as an NFA (states 0..n, `accepting` marks terminal states) rather
+/// than a determinized DFA: one character can lead to several states at once
+/// (in `[A-Z0-9]{1,2}[0-9][A-Z]{1,3}`, a digit after the first character may
+/// either extend the first group or fill the second), so the full state set
+/// is simulated directly instead of running a subset-construction step.
+#[derive(Debug, Clone)]
+pub struct Grammar {
+    /// `transitions[state]` is the list of `(class, target)` edges out of `state`.
+    transitions: Vec<Vec<(CharClass, usize)>>,
+    accepting: Vec<bool>,
+}
+
+impl Grammar {
+    /// Compile a grammar matching exactly the concatenation of `groups`, each
+    /// repeated within its own `{min,max}` bound (e.g. a callsign pattern).
+    pub fn from_groups(groups: &[GrammarGroup]) -> Self {
+        let mut transitions: Vec<Vec<(CharClass, usize)>> = vec![Vec::new()];
+        let mut accepting = vec![false];
+
+        let mut frontier = vec![0usize];
+        for group in groups {
+            let mut current_states = frontier.clone();
+            let mut next_frontier = Vec::new();
+
+            for rep in 0..group.max {
+                let mut new_states = Vec::new();
+                for &state in &current_states {
+                    let next_state = transitions.len();
+                    transitions.push(Vec::new());
+                    accepting.push(false);
+                    transitions[state].push((group.class.clone(), next_state));
+                    new_states.push(next_state);
+                }
+                current_states = new_states;
+                if rep + 1 >= group.min {
+                    next_frontier.extend(current_states.iter().copied());
+                }
+            }
+
+            frontier = next_frontier;
+        }
+
+        for state in &frontier {
+            accepting[*state] = true;
+        }
+
+        Self {
+            transitions,
+            accepting,
+        }
+    }
+
+    /// Compile a grammar accepting exactly one of `words` (e.g. a Q-code set),
+    /// sharing common prefixes the same way [`crate::correction::Lexicon`] does.
+    pub fn alternation(words: &[&str]) -> Self {
+        let mut transitions: Vec<Vec<(CharClass, usize)>> = vec![Vec::new()];
+        let mut accepting = vec![false];
+
+        for word in words {
+            let mut current = 0usize;
+            for ch in word.chars() {
+                let ch = ch.to_ascii_uppercase();
+                let existing = transitions[current]
+                    .iter()
+                    .find(|(class, _)| class.contains(ch))
+                    .map(|(_, target)| *target);
+                current = match existing {
+                    Some(target) => target,
+                    None => {
+                        let target = transitions.len();
+                        transitions.push(Vec::new());
+                        accepting.push(false);
+                        transitions[current].push((CharClass::new(&ch.to_string()), target));
+                        target
+                    }
+                };
+            }
+            accepting[current] = true;
+        }
+
+        Self {
+            transitions,
+            accepting,
+        }
+    }
+
+    /// The state set a fresh hypothesis starts in.
+    pub fn start(&self) -> GrammarState {
+        GrammarState {
+            states: BTreeSet::from([0]),
+        }
+    }
+
+    /// Advance every active state on `ch`, returning the new (possibly empty)
+    /// state set. An empty result means `ch` leaves every path the grammar
+    /// allows.
+    pub fn step(&self, state: &GrammarState, ch: char) -> GrammarState {
+        let mut next = BTreeSet::new();
+        for &from in &state.states {
+            for (class, target) in &self.transitions[from] {
+                if class.contains(ch) {
+                    next.insert(*target);
+                }
+            }
+        }
+        GrammarState { states: next }
+    }
+
+    /// Whether `state` has already left every accepting path.
+    pub fn is_dead(&self, state: &GrammarState) -> bool {
+        state.states.is_empty()
+    }
+
+    /// Whether `state` could end the input right now in an accepting position.
+    pub fn is_accepting(&self, state: &GrammarState) -> bool {
+        state.states.iter().any(|&s| self.accepting[s])
+    }
+}
+
+/// The set of [`Grammar`] NFA states a single beam-search hypothesis is
+/// currently consistent with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrammarState {
+    states: BTreeSet<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_groups_matches_callsign_pattern() {
+        // [A-Z0-9]{1,2}[0-9]{1,1}[A-Z]{1,3}, e.g. "W1AW" or "VE3ABC".
+        let grammar = Grammar::from_groups(&[
+            GrammarGroup::new(CharClass::range('A', 'Z').union(CharClass::range('0', '9')), 1, 2),
+            GrammarGroup::new(CharClass::range('0', '9'), 1, 1),
+            GrammarGroup::new(CharClass::range('A', 'Z'), 1, 3),
+        ]);
+
+        let mut state = grammar.start();
+        for ch in "W1AW".chars() {
+            state = grammar.step(&state, ch);
+            assert!(!grammar.is_dead(&state), "died on '{ch}'");
+        }
+        assert!(grammar.is_accepting(&state));
+    }
+
+    #[test]
+    fn test_from_groups_rejects_out_of_grammar_input() {
+        let grammar = Grammar::from_groups(&[GrammarGroup::new(CharClass::range('A', 'Z'), 1, 1)]);
+        let state = grammar.step(&grammar.start(), '1');
+        assert!(grammar.is_dead(&state));
+    }
+
+    #[test]
+    fn test_alternation_accepts_only_listed_words() {
+        let grammar = Grammar::alternation(&["QTH", "QRZ", "QSL"]);
+
+        let mut state = grammar.start();
+        for ch in "QTH".chars() {
+            state = grammar.step(&state, ch);
+        }
+        assert!(grammar.is_accepting(&state));
+
+        let mut state = grammar.start();
+        for ch in "QRM".chars() {
+            state = grammar.step(&state, ch);
+        }
+        assert!(grammar.is_dead(&state));
+    }
+}
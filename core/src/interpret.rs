@@ -1,4 +1,6 @@
+use crate::grammar::{CharClass, Grammar, GrammarGroup, GrammarState};
 use crate::types::*;
+use std::collections::HashMap;
 use std::f32::consts::PI;
 
 // === PHASE 3 CONSTANTS ===
@@ -9,9 +11,18 @@ const DEFAULT_SPACE_PENALTY: f32 = 0.3; // Reduced from 0.5 - be less reluctant
 const DEFAULT_LM_WEIGHT: f32 = 2.0; // Increased from 1.0 - trust language model more
 const DEFAULT_LATE_INTRA_PENALTY: f32 = 0.5; // Reduced from 0.7 - be more forgiving of timing
 const DEFAULT_LONG_INTER_PENALTY: f32 = 0.6; // Reduced from 0.8 - be more forgiving of timing
+const DEFAULT_TIMING_WEIGHT: f32 = 1.0; // Scale on the probabilistic timing NLL
 
 // Language Model Parameters
 const DEFAULT_UNKNOWN_TRIGRAM_COST: f32 = 8.0;
+/// Highest n-gram order the back-off model supports (context length 3 + the
+/// character being predicted).
+const MAX_LM_ORDER: usize = 4;
+/// Default query order - matches the original fixed trigram model.
+const DEFAULT_LM_ORDER: usize = 3;
+/// Stupid-backoff discount `alpha`: the probability mass assumed when
+/// falling back to the next lower order on a miss.
+const LM_BACKOFF_ALPHA: f32 = 0.4;
 
 // Timing Thresholds (multipliers of unit time T)
 const LATE_INTRA_THRESHOLD_MULTIPLIER: f32 = 2.0;
@@ -24,6 +35,21 @@ const DEFAULT_TIMING_TRACKER_ALPHA: f32 = 0.1;
 
 // Confidence Calculation Parameters
 
+/// Geometric mean of `values`; `0.0` for an empty slice.
+///
+/// Used to roll per-character posterior confidences (see
+/// [`record_char_confidence`]) into a single whole-message confidence: a
+/// geometric mean punishes a single low-confidence character much more than
+/// an arithmetic mean would, matching the intuition that one badly garbled
+/// letter should tank trust in the whole decode.
+fn geometric_mean(values: &[f32]) -> f32 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let sum_ln: f32 = values.iter().map(|v| v.max(1e-6).ln()).sum();
+    (sum_ln / values.len() as f32).exp()
+}
+
 /// Timing statistics for adaptive analysis
 #[derive(Debug, Clone)]
 struct TimingStats {
@@ -96,6 +122,15 @@ impl TimingTracker {
         self.ln_t = (1.0 - self.alpha) * self.ln_t + self.alpha * target_ln_t;
     }
 
+    /// Update the tracked unit from a duration already known to be `ratio`
+    /// units long (e.g. `3.0` for an inter-character gap, `7.0` for a word
+    /// gap), same EWMA scheme as [`update_from_on_signal`]. Used to track
+    /// the spacing unit separately from the element unit.
+    fn update_from_ratio(&mut self, duration: f32, ratio: f32) {
+        let target_ln_t = duration.max(1e-6).ln() - ratio.ln();
+        self.ln_t = (1.0 - self.alpha) * self.ln_t + self.alpha * target_ln_t;
+    }
+
     fn get_ln_t(&self) -> f32 {
         self.ln_t
     }
@@ -357,14 +392,48 @@ fn get_morse_trie() -> &'static MorseTrie {
 
 // === PHASE 3: BEAM SEARCH + LANGUAGE MODEL ===
 
+/// Sparse decomposition of a hypothesis cost into its unweighted components.
+///
+/// The scalar `Hypothesis::cost` is `features · weights` for the weights in
+/// [`BeamSearchParams`], plus any grammar penalty (which has no feature
+/// component). Tracking the raw components alongside the scalar lets the
+/// discriminative tuner (see [`train`]) recompute the cost under trial
+/// weights and take large-margin steps on `features(prediction) −
+/// features(oracle)` without re-running the decoder.
+#[derive(Debug, Clone, Default)]
+struct FeatureVector {
+    /// Summed timing negative log-likelihood (element + gap classification).
+    timing_nll: f32,
+    /// Summed language-model negative log-likelihood.
+    lm_nll: f32,
+    /// Number of discretionary spaces inserted (those charged `space_penalty`).
+    inserted_spaces: f32,
+    /// Count of late intra-character gaps charged `late_intra_penalty`.
+    late_intra_count: f32,
+    /// Count of long inter-character gaps charged `long_inter_penalty`.
+    long_inter_count: f32,
+}
+
+impl FeatureVector {
+    /// Cost of this feature vector under the given weights.
+    fn dot(&self, params: &BeamSearchParams) -> f32 {
+        self.timing_nll * params.timing_weight
+            + self.lm_nll * params.lm_weight
+            + self.inserted_spaces * params.space_penalty
+            + self.late_intra_count * params.late_intra_penalty
+            + self.long_inter_count * params.long_inter_penalty
+    }
+}
+
 /// Beam search hypothesis for multiple interpretation paths
 #[derive(Debug, Clone)]
 struct Hypothesis {
     /// Current position in morse trie (0 = root)
     trie_node: u16,
 
-    /// Last 2 characters for trigram language model context
-    lm_context: [u8; 2],
+    /// Last `MAX_LM_ORDER - 1` characters for the back-off language model
+    /// context, most recent last.
+    lm_context: [u8; MAX_LM_ORDER - 1],
 
     /// Number of characters in current word (since last space)
     pending_word_len: u16,
@@ -372,8 +441,15 @@ struct Hypothesis {
     /// Accumulated cost: timing + language + spacing penalties
     cost: f32,
 
+    /// Unweighted feature decomposition of `cost` (for discriminative tuning)
+    features: FeatureVector,
+
     /// Decoded text output so far
     text: String,
+
+    /// Active [`Grammar`] NFA states consistent with `text` so far, if a
+    /// grammar is attached to the decoder; `None` otherwise.
+    grammar_state: Option<GrammarState>,
 }
 
 impl Hypothesis {
@@ -381,21 +457,33 @@ impl Hypothesis {
     fn new() -> Self {
         Self {
             trie_node: MorseTrie::ROOT,
-            lm_context: [b' ', b' '], // Start with spaces for context
+            lm_context: [b' '; MAX_LM_ORDER - 1], // Start with spaces for context
             pending_word_len: 0,
             cost: 0.0,
+            features: FeatureVector::default(),
             text: String::new(),
+            grammar_state: None,
         }
     }
 
-    /// Add a character to the hypothesis and update language model context
-    fn add_character(&mut self, ch: char, lm_cost: f32) {
+    /// Accumulate a timing negative-log-likelihood, weighted by `timing_weight`.
+    fn add_timing_cost(&mut self, nll: f32, timing_weight: f32) {
+        self.features.timing_nll += nll;
+        self.cost += nll * timing_weight;
+    }
+
+    /// Add a character to the hypothesis and update language model context.
+    ///
+    /// `lm_nll` is the raw (unweighted) language-model cost; it is scaled by
+    /// `lm_weight` for the running scalar cost and tracked raw for tuning.
+    fn add_character(&mut self, ch: char, lm_nll: f32, lm_weight: f32) {
         self.text.push(ch);
-        self.cost += lm_cost;
+        self.features.lm_nll += lm_nll;
+        self.cost += lm_nll * lm_weight;
 
-        // Update trigram context (shift left, add new char)
-        self.lm_context[0] = self.lm_context[1];
-        self.lm_context[1] = if ch.is_ascii() { ch as u8 } else { b'?' };
+        // Update the back-off context (shift left, add new char)
+        self.lm_context.rotate_left(1);
+        *self.lm_context.last_mut().unwrap() = if ch.is_ascii() { ch as u8 } else { b'?' };
 
         if ch == ' ' {
             self.pending_word_len = 0;
@@ -407,68 +495,115 @@ impl Hypothesis {
         self.trie_node = MorseTrie::ROOT;
     }
 
+    /// Charge the discretionary-space penalty and record the inserted space.
+    fn add_space_penalty(&mut self, space_penalty: f32) {
+        self.features.inserted_spaces += 1.0;
+        self.cost += space_penalty;
+    }
+
     /// Clone hypothesis for beam search expansion
     fn fork(&self) -> Self {
         self.clone()
     }
 }
 
-/// Simple character trigram language model with embedded English data
+/// Variable-order character n-gram language model with embedded English data
+/// and Stupid Backoff (à la KenLM/ff_ngrams).
+///
+/// `ngrams[k - 1]` holds order-`k` grams (`k - 1` context bytes followed by
+/// the predicted byte) for `k` in `1..=MAX_LM_ORDER`. A query at order `k`
+/// that misses backs off to order `k - 1`, discounting the lower-order cost
+/// by `-ln(LM_BACKOFF_ALPHA)` in this negative-log-cost space (equivalent to
+/// scaling the probability by `LM_BACKOFF_ALPHA`), bottoming out at
+/// `default_cost` if even the unigram is unseen.
 struct LanguageModel {
-    /// Trigram costs: (char1, char2, char3) -> negative log probability
-    trigrams: std::collections::HashMap<(u8, u8, u8), f32>,
+    /// Gram costs per order, keyed by the gram's raw bytes (context + target).
+    ngrams: Vec<HashMap<Vec<u8>, f32>>,
 
-    /// Default cost for unknown trigrams
+    /// Default cost when even the unigram is unseen
     default_cost: f32,
 }
 
 impl LanguageModel {
-    /// Create English language model with common trigrams
+    /// Create English language model with common n-grams
     /// Based on frequency analysis of English text
     fn new() -> Self {
         let mut lm = Self {
-            trigrams: std::collections::HashMap::new(),
+            ngrams: (0..MAX_LM_ORDER).map(|_| HashMap::new()).collect(),
             default_cost: DEFAULT_UNKNOWN_TRIGRAM_COST,
         };
 
-        // Load common English trigrams with frequency-based costs
-        // Format: trigram, frequency_rank -> lower rank = lower cost
-        lm.load_english_trigrams();
+        // Load common English n-grams with frequency-based costs
+        lm.load_english_ngrams();
 
-        // Add morse-specific patterns not in general English text
-        lm.add_trigram_cost(b"SOS", 0.5); // Very common morse pattern
-        lm.add_trigram_cost(b"CQC", 0.8); // Ham radio
-        lm.add_trigram_cost(b"CQ ", 0.3); // CQ call
-        lm.add_trigram_cost(b"QSO", 1.0); // Ham radio conversation
+        // Add morse-specific trigram patterns not in general English text
+        lm.add_ngram_cost(b"SOS", 0.5); // Very common morse pattern
+        lm.add_ngram_cost(b"CQC", 0.8); // Ham radio
+        lm.add_ngram_cost(b"CQ ", 0.3); // CQ call
+        lm.add_ngram_cost(b"QSO", 1.0); // Ham radio conversation
 
         lm
     }
 
-    /// Load common English trigrams from build-time generated data
-    fn load_english_trigrams(&mut self) {
-        // Include the generated trigram data
+    /// Load common English n-grams from build-time generated data.
+    ///
+    /// Orders 1-3 are marginalized at build time from the same trigram
+    /// frequency corpus (see `build.rs`); order 4 has no source data yet, so
+    /// queries at that order always back off to the trigram table.
+    fn load_english_ngrams(&mut self) {
+        let unigram_data: &[(&str, f32)] = include!(concat!(env!("OUT_DIR"), "/unigrams.rs"));
+        self.load_ngram_table(unigram_data, 1);
+
+        let bigram_data: &[(&str, f32)] = include!(concat!(env!("OUT_DIR"), "/bigrams.rs"));
+        self.load_ngram_table(bigram_data, 2);
+
         let trigram_data: &[(&str, f32)] = include!(concat!(env!("OUT_DIR"), "/trigrams.rs"));
+        self.load_ngram_table(trigram_data, 3);
+    }
 
-        for &(trigram_str, cost) in trigram_data {
-            let bytes = trigram_str.as_bytes();
-            if bytes.len() == 3 {
-                self.trigrams.insert((bytes[0], bytes[1], bytes[2]), cost);
+    /// Load a generated `(gram_str, cost)` table into the order-`order` map,
+    /// skipping any entry whose length doesn't match.
+    fn load_ngram_table(&mut self, data: &[(&str, f32)], order: usize) {
+        for &(gram_str, cost) in data {
+            let bytes = gram_str.as_bytes();
+            if bytes.len() == order {
+                self.ngrams[order - 1].insert(bytes.to_vec(), cost);
             }
         }
     }
 
-    /// Add a specific trigram with cost
-    fn add_trigram_cost(&mut self, trigram: &[u8; 3], cost: f32) {
-        self.trigrams
-            .insert((trigram[0], trigram[1], trigram[2]), cost);
+    /// Add a specific n-gram with cost; the order is inferred from `gram`'s length.
+    fn add_ngram_cost(&mut self, gram: &[u8], cost: f32) {
+        self.ngrams[gram.len() - 1].insert(gram.to_vec(), cost);
     }
 
-    /// Get language model cost for completing a trigram
-    fn get_cost(&self, context: [u8; 2], next_char: u8) -> f32 {
-        self.trigrams
-            .get(&(context[0], context[1], next_char))
-            .copied()
-            .unwrap_or(self.default_cost)
+    /// Get the language model cost for `next_char` following `context`,
+    /// querying at `order` and backing off to lower orders on a miss.
+    ///
+    /// `context` holds the available history, most recent last; `order` is
+    /// clamped to `[1, MAX_LM_ORDER]` and to `context.len() + 1`.
+    fn get_cost(&self, context: &[u8], next_char: u8, order: usize) -> f32 {
+        let order = order.clamp(1, MAX_LM_ORDER).min(context.len() + 1);
+        self.cost_at_order(context, next_char, order)
+    }
+
+    fn cost_at_order(&self, context: &[u8], next_char: u8, order: usize) -> f32 {
+        if order == 0 {
+            return self.default_cost;
+        }
+
+        let ctx_len = order - 1;
+        let mut gram = Vec::with_capacity(order);
+        gram.extend_from_slice(&context[context.len() - ctx_len..]);
+        gram.push(next_char);
+
+        if let Some(&cost) = self.ngrams[order - 1].get(&gram) {
+            return cost;
+        }
+
+        // Stupid Backoff: scale probability by alpha, i.e. add -ln(alpha) in
+        // this negative-log-cost space, and recurse to the next lower order.
+        -LM_BACKOFF_ALPHA.ln() + self.cost_at_order(context, next_char, order - 1)
     }
 }
 
@@ -480,22 +615,33 @@ fn get_language_model() -> &'static LanguageModel {
 }
 
 /// Beam search parameters for Phase 3
+///
+/// Public so the weights fitted by [`train`] can be handed back to a caller
+/// (e.g. for serialization alongside an operator profile). Decoding itself
+/// goes through [`morse_interpret`]/[`morse_interpret_n_best`]/[`MorseDecoder`],
+/// each of which builds its own default-weighted decoder internally.
 #[derive(Debug, Clone)]
-struct BeamSearchParams {
+pub struct BeamSearchParams {
     /// Maximum number of hypotheses to maintain
-    beam_size: usize,
+    pub beam_size: usize,
 
     /// Cost penalty for inserting spaces
-    space_penalty: f32,
+    pub space_penalty: f32,
 
     /// Weight of language model relative to timing costs
-    lm_weight: f32,
+    pub lm_weight: f32,
 
     /// Penalty for late intra-character gaps (should be dots/dashes)
-    late_intra_penalty: f32,
+    pub late_intra_penalty: f32,
 
     /// Penalty for long inter-character gaps without inserting space
-    long_inter_penalty: f32,
+    pub long_inter_penalty: f32,
+
+    /// Weight applied to the probabilistic timing NLL
+    pub timing_weight: f32,
+
+    /// N-gram order queried against the back-off language model (1-4)
+    pub lm_order: usize,
 }
 
 impl Default for BeamSearchParams {
@@ -506,6 +652,8 @@ impl Default for BeamSearchParams {
             lm_weight: DEFAULT_LM_WEIGHT,
             late_intra_penalty: DEFAULT_LATE_INTRA_PENALTY,
             long_inter_penalty: DEFAULT_LONG_INTER_PENALTY,
+            timing_weight: DEFAULT_TIMING_WEIGHT,
+            lm_order: DEFAULT_LM_ORDER,
         }
     }
 }
@@ -527,14 +675,114 @@ struct BeamSearchDecoder {
     /// Timing tracker for online adaptation
     timing_tracker: TimingTracker,
 
+    /// Online tracker for the spacing unit (`T_space`), tracked separately
+    /// from `timing_tracker` (`T_elem`) to detect and follow Farnsworth-style
+    /// sending, where inter-character/word gaps run at a different speed
+    /// than the elements themselves.
+    spacing_tracker: TimingTracker,
+
     /// Probabilistic timing model for gap classification
     timing_model: ProbabilisticTimingModel,
+
+    /// Posterior confidence of each emitted character so far, in emission
+    /// order (see [`record_char_confidence`]).
+    char_confidences: Vec<f32>,
+
+    /// Number of characters already scored into `char_confidences`, so a
+    /// beam snapshot that hasn't grown since the last check is a no-op.
+    last_recorded_len: usize,
+
+    /// Optional finite-state grammar (see [`crate::grammar::Grammar`])
+    /// biasing hypotheses toward a known structured format; `None` decodes
+    /// free text as before.
+    grammar: Option<Grammar>,
+
+    /// Cost charged the moment a hypothesis's [`Hypothesis::grammar_state`]
+    /// leaves every accepting path, and again if it finalizes outside one.
+    /// Only meaningful when `grammar` is `Some`.
+    grammar_penalty: f32,
+}
+
+/// Score the posterior confidence of every character newly emitted by the
+/// consensus (lowest-cost) hypothesis in `candidates` since `*last_recorded_len`,
+/// appending one entry per new character to `*char_confidences`.
+///
+/// For a given character position, confidence is the softmax mass (over
+/// `-cost`, numerically stabilized against the candidates' minimum cost) of
+/// hypotheses that agree with the consensus hypothesis's character at that
+/// position, divided by the total mass of *all* candidates -- hypotheses
+/// that haven't completed that character yet (e.g. a fork still waiting out
+/// an ambiguous gap) count against the agreeing mass, not for it. This turns
+/// the log-normal element/gap likelihoods the beam already computes into a
+/// per-character uncertainty estimate instead of discarding them at the
+/// `classify_*_min_cost` step.
+fn record_char_confidence(
+    char_confidences: &mut Vec<f32>,
+    last_recorded_len: &mut usize,
+    candidates: &[Hypothesis],
+) {
+    let Some(consensus) = candidates
+        .iter()
+        .min_by(|a, b| a.cost.partial_cmp(&b.cost).unwrap_or(std::cmp::Ordering::Equal))
+    else {
+        return;
+    };
+    let consensus_chars: Vec<char> = consensus.text.chars().collect();
+    if consensus_chars.len() <= *last_recorded_len {
+        return;
+    }
+
+    let min_cost = candidates
+        .iter()
+        .map(|h| h.cost)
+        .fold(f32::INFINITY, f32::min);
+    let masses: Vec<f32> = candidates
+        .iter()
+        .map(|h| (-(h.cost - min_cost)).exp())
+        .collect();
+    let total_mass: f32 = masses.iter().sum();
+
+    for position in *last_recorded_len..consensus_chars.len() {
+        if total_mass <= 0.0 {
+            char_confidences.push(0.0);
+            continue;
+        }
+        let agree_mass: f32 = candidates
+            .iter()
+            .zip(&masses)
+            .filter(|(h, _)| h.text.chars().nth(position) == Some(consensus_chars[position]))
+            .map(|(_, mass)| mass)
+            .sum();
+        char_confidences.push(agree_mass / total_mass);
+    }
+    *last_recorded_len = consensus_chars.len();
+}
+
+/// Step `hyp`'s grammar state for a character just finalized via
+/// [`Hypothesis::add_character`], charging `grammar_penalty` once, the
+/// moment the hypothesis leaves every accepting path. No-op if `grammar` is
+/// `None`. A free function (not a `BeamSearchDecoder` method) so it can be
+/// called alongside a `&self.hypotheses`/`&mut self.hypotheses` borrow
+/// without conflicting with it, the same reason [`record_char_confidence`] is
+/// a free function.
+fn apply_grammar(grammar: &Option<Grammar>, grammar_penalty: f32, hyp: &mut Hypothesis, ch: char) {
+    let Some(grammar) = grammar else { return };
+    let was_dead = hyp
+        .grammar_state
+        .as_ref()
+        .is_some_and(|state| grammar.is_dead(state));
+    let state = hyp.grammar_state.get_or_insert_with(|| grammar.start());
+    *state = grammar.step(state, ch);
+    if !was_dead && grammar.is_dead(state) {
+        hyp.cost += grammar_penalty;
+    }
 }
 
 impl BeamSearchDecoder {
     /// Create new beam search decoder with adaptive gap clustering
     fn new(timings: &MorseTimings, params: BeamSearchParams) -> Self {
         let timing_tracker = TimingTracker::new(timings.dot_duration);
+        let spacing_tracker = TimingTracker::new(timings.spacing_duration);
         let timing_model = ProbabilisticTimingModel::from_tracker_and_clusters(
             &timing_tracker,
             timings.gap_clusters.clone(),
@@ -546,7 +794,12 @@ impl BeamSearchDecoder {
             trie: get_morse_trie(),
             lm: get_language_model(),
             timing_tracker,
+            spacing_tracker,
             timing_model,
+            char_confidences: Vec::new(),
+            last_recorded_len: 0,
+            grammar: None,
+            grammar_penalty: 0.0,
         };
 
         // Ensure we start with exactly one hypothesis
@@ -554,6 +807,39 @@ impl BeamSearchDecoder {
         decoder
     }
 
+    /// Attach a grammar constraint (see [`crate::grammar::Grammar`]),
+    /// biasing hypotheses toward its accepting paths. `penalty` controls how
+    /// hard the constraint is: `0.0` disables it, a small value softly
+    /// biases the search, a very large value effectively filters hypotheses
+    /// that leave the grammar.
+    fn with_grammar(mut self, grammar: Grammar, penalty: f32) -> Self {
+        self.grammar = Some(grammar);
+        self.grammar_penalty = penalty;
+        self
+    }
+
+    /// Charge `grammar_penalty` against every surviving hypothesis whose
+    /// grammar state is still alive but not accepting, so
+    /// [`finalize`](Self::finalize)/[`finalize_nbest`](Self::finalize_nbest)
+    /// prefer complete matches over partial ones. Hypotheses that already
+    /// left every accepting path paid the penalty once in [`apply_grammar`]
+    /// and are not charged again; hypotheses with no grammar state yet (no
+    /// character emitted) are not charged. No-op if no grammar is attached.
+    fn penalize_non_accepting(&self) -> Vec<f32> {
+        let Some(grammar) = &self.grammar else {
+            return vec![0.0; self.hypotheses.len()];
+        };
+        self.hypotheses
+            .iter()
+            .map(|hyp| match &hyp.grammar_state {
+                Some(state) if !grammar.is_dead(state) && !grammar.is_accepting(state) => {
+                    self.grammar_penalty
+                }
+                _ => 0.0,
+            })
+            .collect()
+    }
+
     /// Process an ON signal (dot or dash) - expand hypotheses in trie
     fn process_on_signal(&mut self, signal: &MorseSignal) {
         let element = self.timing_model.classify_element_min_cost(signal.seconds);
@@ -568,7 +854,7 @@ impl BeamSearchDecoder {
                 let element_costs = self.timing_model.element_costs(signal.seconds);
                 for (elem_type, cost) in element_costs {
                     if elem_type == element {
-                        new_hyp.cost += cost;
+                        new_hyp.add_timing_cost(cost, self.params.timing_weight);
                         break;
                     }
                 }
@@ -584,6 +870,14 @@ impl BeamSearchDecoder {
     /// Process an OFF signal (gap) - handle character/word completion and spacing
     fn process_off_signal(&mut self, signal: &MorseSignal) {
         let gap_type = self.timing_model.classify_gap_min_cost(signal.seconds);
+        // Track the spacing unit from gaps classified as inter-character/word,
+        // independently of the element unit, so Farnsworth-style sending (fast
+        // characters, slow spacing) is followed rather than misread.
+        match gap_type {
+            GapType::InterCharacter => self.spacing_tracker.update_from_ratio(signal.seconds, 3.0),
+            GapType::Word => self.spacing_tracker.update_from_ratio(signal.seconds, 7.0),
+            GapType::IntraCharacter => {}
+        }
         let mut new_hypotheses = Vec::new();
 
         for hyp in &self.hypotheses {
@@ -595,7 +889,7 @@ impl BeamSearchDecoder {
                     let gap_costs = self.timing_model.gap_costs(signal.seconds);
                     for (gap_type_cost, cost) in gap_costs {
                         if gap_type_cost == gap_type {
-                            new_hyp.cost += cost;
+                            new_hyp.add_timing_cost(cost, self.params.timing_weight);
                             break;
                         }
                     }
@@ -604,6 +898,7 @@ impl BeamSearchDecoder {
                     if signal.seconds
                         > self.timing_tracker.get_t() * LATE_INTRA_THRESHOLD_MULTIPLIER
                     {
+                        new_hyp.features.late_intra_count += 1.0;
                         new_hyp.cost += self.params.late_intra_penalty;
                     }
 
@@ -613,14 +908,16 @@ impl BeamSearchDecoder {
                     // Medium gap - complete character, don't add space
                     if let Some(ch) = self.trie.get_terminal(hyp.trie_node) {
                         let mut new_hyp = hyp.fork();
-                        let lm_cost =
-                            self.lm.get_cost(new_hyp.lm_context, ch as u8) * self.params.lm_weight;
-                        new_hyp.add_character(ch, lm_cost);
+                        let lm_nll =
+                            self.lm
+                                .get_cost(&new_hyp.lm_context, ch as u8, self.params.lm_order);
+                        new_hyp.add_character(ch, lm_nll, self.params.lm_weight);
+                        apply_grammar(&self.grammar, self.grammar_penalty, &mut new_hyp, ch);
                         // Get the cost for inter-character gap classification
                         let gap_costs = self.timing_model.gap_costs(signal.seconds);
                         for (gap_type_cost, cost) in gap_costs {
                             if gap_type_cost == gap_type {
-                                new_hyp.cost += cost;
+                                new_hyp.add_timing_cost(cost, self.params.timing_weight);
                                 break;
                             }
                         }
@@ -631,18 +928,27 @@ impl BeamSearchDecoder {
                     if hyp.pending_word_len > LONG_WORD_LENGTH_THRESHOLD {
                         if let Some(ch) = self.trie.get_terminal(hyp.trie_node) {
                             let mut space_hyp = hyp.fork();
-                            let ch_cost = self.lm.get_cost(space_hyp.lm_context, ch as u8)
-                                * self.params.lm_weight;
-                            space_hyp.add_character(ch, ch_cost);
-
-                            let space_cost = self.lm.get_cost(space_hyp.lm_context, b' ')
-                                * self.params.lm_weight;
-                            space_hyp.add_character(' ', space_cost + self.params.space_penalty);
+                            let ch_nll = self.lm.get_cost(
+                                &space_hyp.lm_context,
+                                ch as u8,
+                                self.params.lm_order,
+                            );
+                            space_hyp.add_character(ch, ch_nll, self.params.lm_weight);
+                            apply_grammar(&self.grammar, self.grammar_penalty, &mut space_hyp, ch);
+
+                            let space_nll = self.lm.get_cost(
+                                &space_hyp.lm_context,
+                                b' ',
+                                self.params.lm_order,
+                            );
+                            space_hyp.add_character(' ', space_nll, self.params.lm_weight);
+                            apply_grammar(&self.grammar, self.grammar_penalty, &mut space_hyp, ' ');
+                            space_hyp.add_space_penalty(self.params.space_penalty);
                             // Get the cost for gap classification with space
                             let gap_costs = self.timing_model.gap_costs(signal.seconds);
                             for (gap_type_cost, cost) in gap_costs {
                                 if gap_type_cost == gap_type {
-                                    space_hyp.cost += cost;
+                                    space_hyp.add_timing_cost(cost, self.params.timing_weight);
                                     break;
                                 }
                             }
@@ -654,18 +960,22 @@ impl BeamSearchDecoder {
                     // Long gap - complete character and add space
                     if let Some(ch) = self.trie.get_terminal(hyp.trie_node) {
                         let mut new_hyp = hyp.fork();
-                        let ch_cost =
-                            self.lm.get_cost(new_hyp.lm_context, ch as u8) * self.params.lm_weight;
-                        new_hyp.add_character(ch, ch_cost);
-
-                        let space_cost =
-                            self.lm.get_cost(new_hyp.lm_context, b' ') * self.params.lm_weight;
-                        new_hyp.add_character(' ', space_cost);
+                        let ch_nll =
+                            self.lm
+                                .get_cost(&new_hyp.lm_context, ch as u8, self.params.lm_order);
+                        new_hyp.add_character(ch, ch_nll, self.params.lm_weight);
+                        apply_grammar(&self.grammar, self.grammar_penalty, &mut new_hyp, ch);
+
+                        let space_nll =
+                            self.lm
+                                .get_cost(&new_hyp.lm_context, b' ', self.params.lm_order);
+                        new_hyp.add_character(' ', space_nll, self.params.lm_weight);
+                        apply_grammar(&self.grammar, self.grammar_penalty, &mut new_hyp, ' ');
                         // Get the cost for word gap classification
                         let gap_costs = self.timing_model.gap_costs(signal.seconds);
                         for (gap_type_cost, cost) in gap_costs {
                             if gap_type_cost == gap_type {
-                                new_hyp.cost += cost;
+                                new_hyp.add_timing_cost(cost, self.params.timing_weight);
                                 break;
                             }
                         }
@@ -682,13 +992,16 @@ impl BeamSearchDecoder {
                 let gap_costs = self.timing_model.gap_costs(signal.seconds);
                 for (gap_type_cost, cost) in gap_costs {
                     if gap_type_cost == GapType::IntraCharacter {
-                        continue_hyp.cost += cost;
+                        continue_hyp.add_timing_cost(cost, self.params.timing_weight);
                         break;
                     }
                 }
 
-                // Add penalty for long gaps without character completion
-                if signal.seconds > self.timing_tracker.get_t() * LONG_INTER_THRESHOLD_MULTIPLIER {
+                // Add penalty for long gaps without character completion. This
+                // compares against the spacing unit (not the element unit), since
+                // under Farnsworth timing the gap itself runs at spacing speed.
+                if signal.seconds > self.spacing_tracker.get_t() * LONG_INTER_THRESHOLD_MULTIPLIER {
+                    continue_hyp.features.long_inter_count += 1.0;
                     continue_hyp.cost += self.params.long_inter_penalty;
                 }
 
@@ -696,6 +1009,11 @@ impl BeamSearchDecoder {
             }
         }
 
+        record_char_confidence(
+            &mut self.char_confidences,
+            &mut self.last_recorded_len,
+            &new_hypotheses,
+        );
         self.hypotheses = new_hypotheses;
         self.prune_beam();
     }
@@ -722,10 +1040,20 @@ impl BeamSearchDecoder {
         // Complete any remaining characters
         for hyp in &mut self.hypotheses {
             if let Some(ch) = self.trie.get_terminal(hyp.trie_node) {
-                let lm_cost = self.lm.get_cost(hyp.lm_context, ch as u8) * self.params.lm_weight;
-                hyp.add_character(ch, lm_cost);
+                let lm_nll = self.lm.get_cost(&hyp.lm_context, ch as u8, self.params.lm_order);
+                hyp.add_character(ch, lm_nll, self.params.lm_weight);
+                apply_grammar(&self.grammar, self.grammar_penalty, hyp, ch);
             }
         }
+        record_char_confidence(
+            &mut self.char_confidences,
+            &mut self.last_recorded_len,
+            &self.hypotheses,
+        );
+        let grammar_penalties = self.penalize_non_accepting();
+        for (hyp, penalty) in self.hypotheses.iter_mut().zip(grammar_penalties) {
+            hyp.cost += penalty;
+        }
 
         // Find hypothesis with lowest total cost
         std::mem::take(&mut self.hypotheses)
@@ -738,39 +1066,407 @@ impl BeamSearchDecoder {
             .unwrap_or_else(Hypothesis::new)
     }
 
+    /// Complete decoding and return the surviving hypotheses, lowest cost
+    /// first. Like [`finalize`](Self::finalize) but keeps the whole beam so
+    /// callers can extract an N-best list or train on it.
+    fn finalize_nbest(&mut self) -> Vec<Hypothesis> {
+        for hyp in &mut self.hypotheses {
+            if let Some(ch) = self.trie.get_terminal(hyp.trie_node) {
+                let lm_nll = self.lm.get_cost(&hyp.lm_context, ch as u8, self.params.lm_order);
+                hyp.add_character(ch, lm_nll, self.params.lm_weight);
+                apply_grammar(&self.grammar, self.grammar_penalty, hyp, ch);
+            }
+        }
+        record_char_confidence(
+            &mut self.char_confidences,
+            &mut self.last_recorded_len,
+            &self.hypotheses,
+        );
+        let grammar_penalties = self.penalize_non_accepting();
+        for (hyp, penalty) in self.hypotheses.iter_mut().zip(grammar_penalties) {
+            hyp.cost += penalty;
+        }
+
+        let mut hypotheses = std::mem::take(&mut self.hypotheses);
+        hypotheses.sort_by(|a, b| {
+            a.cost
+                .partial_cmp(&b.cost)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        hypotheses
+    }
+
+    /// Complete decoding and return the top `k` distinct hypotheses as
+    /// [`Decoding`]s, with softmax-normalized confidences.
+    ///
+    /// Dedup/truncation/scoring is [`dedup_and_rank`]; see there for details.
+    fn best_n(&mut self, k: usize) -> Vec<Decoding> {
+        let hypotheses = self.finalize_nbest();
+        dedup_and_rank(hypotheses, k)
+    }
+}
+
+/// Dedup `hypotheses` by `(text, trie_node, lm_context)` (keeping the
+/// minimum-cost survivor of each group), sort ascending by cost, truncate to
+/// the top `k`, and softmax-score the survivors.
+///
+/// Confidence is `exp(-cost_i) / Σ exp(-cost_j)` over the retained `k`
+/// hypotheses, computed against the minimum cost for numerical stability.
+/// Shared by [`BeamSearchDecoder::best_n`] and [`k_best_posteriors`] so the
+/// two agree on the same beam instead of ranking it two different ways.
+fn dedup_and_rank(hypotheses: Vec<Hypothesis>, k: usize) -> Vec<Decoding> {
+    let mut by_key: HashMap<(String, u16, [u8; MAX_LM_ORDER - 1]), Hypothesis> = HashMap::new();
+    for hyp in hypotheses {
+        let key = (hyp.text.clone(), hyp.trie_node, hyp.lm_context);
+        by_key
+            .entry(key)
+            .and_modify(|existing| {
+                if hyp.cost < existing.cost {
+                    *existing = hyp.clone();
+                }
+            })
+            .or_insert(hyp);
+    }
+
+    let mut deduped: Vec<Hypothesis> = by_key.into_values().collect();
+    deduped.sort_by(|a, b| {
+        a.cost
+            .partial_cmp(&b.cost)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    deduped.truncate(k);
+
+    let min_cost = deduped.first().map(|h| h.cost).unwrap_or(0.0);
+    let exp_scores: Vec<f32> = deduped
+        .iter()
+        .map(|h| (-(h.cost - min_cost)).exp())
+        .collect();
+    let total: f32 = exp_scores.iter().sum();
+
+    deduped
+        .into_iter()
+        .zip(exp_scores)
+        .map(|(hyp, score)| Decoding {
+            text: hyp.text,
+            cost: hyp.cost,
+            confidence: if total > 0.0 { score / total } else { 0.0 },
+        })
+        .collect()
+}
+
+impl BeamSearchDecoder {
+    /// Peek at the current best hypothesis without consuming the beam.
+    ///
+    /// Any in-progress character (a trie node that is already terminal) is
+    /// completed on the returned clone so callers see the latest decoded text.
+    fn best_hypothesis(&self) -> Hypothesis {
+        self.hypotheses
+            .iter()
+            .min_by(|a, b| {
+                a.cost
+                    .partial_cmp(&b.cost)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .map(|best| {
+                let mut hyp = best.clone();
+                if let Some(ch) = self.trie.get_terminal(hyp.trie_node) {
+                    let lm_nll = self.lm.get_cost(&hyp.lm_context, ch as u8, self.params.lm_order);
+                    hyp.add_character(ch, lm_nll, self.params.lm_weight);
+                }
+                hyp
+            })
+            .unwrap_or_else(Hypothesis::new)
+    }
+
     /// Update timing model with new signal (for online adaptation)
     fn update_timing(&mut self, signal: &MorseSignal) {
         if signal.on {
             self.timing_tracker.update_from_on_signal(signal.seconds);
-            // Update probabilistic timing model with new tracker state but keep gap clusters
-            self.timing_model = ProbabilisticTimingModel::from_tracker_and_clusters(
-                &self.timing_tracker,
-                self.timing_model.gap_clusters.clone(),
-            );
+            // Re-anchor the gap thresholds to the freshly tracked dot and
+            // spacing units so a sender who speeds up, slows down, or sends
+            // Farnsworth-style mid-message keeps getting the right
+            // letter/word boundaries (2x dit splits letters, 5x spacing-unit
+            // splits words). The EWMA trackers are the sliding window; the
+            // clusters follow them.
+            let dot = self.timing_tracker.get_t();
+            let spacing = self.spacing_tracker.get_t();
+            let gap_clusters = GapClusters {
+                intra_to_inter_threshold: dot * 2.0,
+                inter_to_word_threshold: spacing * 5.0,
+            };
+            self.timing_model =
+                ProbabilisticTimingModel::from_tracker_and_clusters(&self.timing_tracker, gap_clusters);
         }
     }
 }
 
+// === PHASE 3: DISCRIMINATIVE WEIGHT TUNING ===
+
+// MIRA-style tuning hyper-parameters
+const DEFAULT_TUNE_NBEST: usize = 16; // k-best list size used per example
+const DEFAULT_TUNE_MARGIN_SCALE: f32 = 1.0; // margin per unit of CER difference
+
+impl FeatureVector {
+    /// Feature components in the same order as [`BeamSearchParams::weights`].
+    fn as_array(&self) -> [f32; 5] {
+        [
+            self.timing_nll,
+            self.lm_nll,
+            self.inserted_spaces,
+            self.late_intra_count,
+            self.long_inter_count,
+        ]
+    }
+}
+
+impl BeamSearchParams {
+    /// Trainable weights in a fixed order: timing, LM, space, late-intra,
+    /// long-inter. The non-trainable `beam_size` is left untouched.
+    fn weights(&self) -> [f32; 5] {
+        [
+            self.timing_weight,
+            self.lm_weight,
+            self.space_penalty,
+            self.late_intra_penalty,
+            self.long_inter_penalty,
+        ]
+    }
+
+    /// Overwrite the trainable weights from a [`weights`](Self::weights) array.
+    fn set_weights(&mut self, w: [f32; 5]) {
+        self.timing_weight = w[0];
+        self.lm_weight = w[1];
+        self.space_penalty = w[2];
+        self.late_intra_penalty = w[3];
+        self.long_inter_penalty = w[4];
+    }
+}
+
+/// Character error rate of `hyp` against `reference`: Levenshtein distance
+/// normalized by the reference length, clamped to `[0, 1]`.
+fn character_error_rate(hyp: &str, reference: &str) -> f32 {
+    let reference_chars: Vec<char> = reference.chars().collect();
+    if reference_chars.is_empty() {
+        return if hyp.is_empty() { 0.0 } else { 1.0 };
+    }
+    let hyp_chars: Vec<char> = hyp.chars().collect();
+
+    // Standard edit-distance DP over one rolling row.
+    let mut prev: Vec<usize> = (0..=reference_chars.len()).collect();
+    let mut curr = vec![0usize; reference_chars.len() + 1];
+    for (i, &hc) in hyp_chars.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, &rc) in reference_chars.iter().enumerate() {
+            let sub = prev[j] + usize::from(hc != rc);
+            curr[j + 1] = sub.min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    (prev[reference_chars.len()] as f32 / reference_chars.len() as f32).min(1.0)
+}
+
+/// Decode one training example under `params`, returning the top
+/// [`DEFAULT_TUNE_NBEST`] hypotheses (the full beam can be much larger than
+/// what's useful for oracle/prediction selection).
+fn decode_nbest_for_training(
+    signals: &[MorseSignal],
+    params: &BeamSearchParams,
+) -> Vec<Hypothesis> {
+    let timings = match MorseTimings::from_signals(signals) {
+        Ok(t) => t,
+        Err(_) => return Vec::new(),
+    };
+    let mut decoder = BeamSearchDecoder::new(&timings, params.clone());
+    for signal in signals {
+        decoder.update_timing(signal);
+        if signal.on {
+            decoder.process_on_signal(signal);
+        } else {
+            decoder.process_off_signal(signal);
+        }
+    }
+    let mut nbest = decoder.finalize_nbest();
+    nbest.truncate(DEFAULT_TUNE_NBEST);
+    nbest
+}
+
+/// Online large-margin (MIRA) training of the beam-search weights from a corpus
+/// of `(signals, reference_text)` pairs, adapting the decoder to a particular
+/// operator's fist or band conditions instead of relying on the fixed
+/// [`BeamSearchParams::default`] constants.
+///
+/// For each example we produce the N-best list under the current weights, score
+/// every hypothesis's CER against the reference, and pick the lowest-CER
+/// `oracle` and the model-best `prediction` (lowest cost). When the model fails
+/// to rank the oracle below the prediction by a CER-scaled margin, we take a
+/// MIRA step `w += tau * (features(prediction) − features(oracle))`, clipping
+/// `tau` so the margin constraint is just satisfied and never exceeds `max_step`
+/// (the `C` of the classic MIRA formulation). The step nudges the weights to
+/// make the oracle cheaper than the current prediction. Weight vectors are
+/// averaged across all updates to reduce variance, mirroring the
+/// averaged-perceptron / k-best MIRA loop used by MT tuners.
+pub fn train(
+    examples: &[(Vec<MorseSignal>, String)],
+    passes: usize,
+    max_step: f32,
+) -> BeamSearchParams {
+    let mut params = BeamSearchParams::default();
+    let mut weights = params.weights();
+
+    // Running sum of the weight vector after every example, for averaging.
+    let mut weight_sum = [0.0f32; 5];
+    let mut weight_count = 0u64;
+
+    for _ in 0..passes {
+        for (signals, reference) in examples {
+            params.set_weights(weights);
+            let nbest = decode_nbest_for_training(signals, &params);
+            if nbest.is_empty() {
+                continue;
+            }
+
+            // Prediction = model-best (finalize_nbest is cost-sorted ascending).
+            let prediction = &nbest[0];
+            // Oracle = lowest CER, tie-broken by model cost.
+            let oracle = nbest
+                .iter()
+                .min_by(|a, b| {
+                    let ca = character_error_rate(&a.text, reference);
+                    let cb = character_error_rate(&b.text, reference);
+                    ca.partial_cmp(&cb)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                        .then(a.cost.partial_cmp(&b.cost).unwrap_or(std::cmp::Ordering::Equal))
+                })
+                .unwrap();
+
+            let cer_pred = character_error_rate(&prediction.text, reference);
+            let cer_oracle = character_error_rate(&oracle.text, reference);
+
+            // diff = features(prediction) − features(oracle)
+            let fp = prediction.features.as_array();
+            let fo = oracle.features.as_array();
+            let mut diff = [0.0f32; 5];
+            let mut norm_sq = 0.0f32;
+            for i in 0..5 {
+                diff[i] = fp[i] - fo[i];
+                norm_sq += diff[i] * diff[i];
+            }
+            if norm_sq <= f32::EPSILON {
+                // Prediction already equals the oracle; nothing to learn.
+                weight_sum.iter_mut().zip(weights).for_each(|(s, w)| *s += w);
+                weight_count += 1;
+                continue;
+            }
+
+            // Margin the model should leave between prediction and oracle.
+            let margin = DEFAULT_TUNE_MARGIN_SCALE * (cer_pred - cer_oracle);
+            // Current separation in cost space: how far the oracle already
+            // sits below the prediction (never positive here, since the
+            // prediction is the cheaper one).
+            let separation = prediction.cost - oracle.cost;
+            let violation = margin - separation;
+            if violation > 0.0 {
+                let tau = (violation / norm_sq).min(max_step);
+                for i in 0..5 {
+                    weights[i] += tau * diff[i];
+                }
+            }
+
+            weight_sum.iter_mut().zip(weights).for_each(|(s, w)| *s += w);
+            weight_count += 1;
+        }
+    }
+
+    if weight_count > 0 {
+        let mut averaged = [0.0f32; 5];
+        for i in 0..5 {
+            averaged[i] = weight_sum[i] / weight_count as f32;
+        }
+        params.set_weights(averaged);
+    }
+    params
+}
+
+/// Map an accumulated hypothesis cost to a confidence in [0, 1].
+///
+/// Lower costs indicate higher confidence, but costs can be negative due to LM
+/// bonuses. The mapping is piecewise-linear over observed cost ranges:
+/// negative (good English) > 0.9, cost 0-3 → 0.85-0.95, 3-8 → 0.75-0.85, and a
+/// slow decline below 0.7 thereafter.
+fn estimate_confidence(cost: f32, text_len: usize) -> f32 {
+    if text_len == 0 {
+        return 0.0;
+    }
+
+    let avg_cost_per_char = cost / text_len as f32;
+
+    if avg_cost_per_char < 0.0 {
+        0.95 + (-avg_cost_per_char * 0.005).min(0.05)
+    } else if avg_cost_per_char <= 3.0 {
+        0.95 - avg_cost_per_char * 0.033
+    } else if avg_cost_per_char <= 8.0 {
+        0.85 - (avg_cost_per_char - 3.0) * 0.02
+    } else {
+        0.7 - (avg_cost_per_char - 8.0) * 0.01
+    }
+    .max(0.0)
+}
+
+/// Maximum length of the `(text, posterior)` k-best list.
+///
+/// Caps `result.k_best`: large enough to show a handful of alternates, far
+/// below the full `DEFAULT_BEAM_SIZE` beam.
+const DEFAULT_K_BEST: usize = 10;
+
+/// Rank the surviving beam as a `(text, posterior)` k-best list, truncated
+/// to [`DEFAULT_K_BEST`]. Delegates to [`dedup_and_rank`] (the same
+/// `(text, trie_node, lm_context)` dedup and softmax scoring
+/// [`BeamSearchDecoder::best_n`] uses) so this list agrees with
+/// `morse_interpret_n_best` on the same beam instead of ranking it
+/// independently. The first entry is always the overall best decoding.
+fn k_best_posteriors(hypotheses: &[Hypothesis]) -> Vec<(String, f32)> {
+    dedup_and_rank(hypotheses.to_vec(), DEFAULT_K_BEST)
+        .into_iter()
+        .map(|decoding| (decoding.text, decoding.confidence))
+        .collect()
+}
+
 /// Parse morse signals using beam search + language model (Phase 3)
 fn parse_morse_signals_beam_search(
     signals: &[MorseSignal],
     timings: &MorseTimings,
     max_output_length: usize,
+    grammar: Option<Grammar>,
+    grammar_penalty: f32,
 ) -> MorseInterpretResult {
     let mut result = MorseInterpretResult {
         text: String::new(),
         confidence: 0.0,
         signals_processed: 0,
         patterns_recognized: 0,
+        estimated_wpm: 0.0,
+        char_confidences: Vec::new(),
+        k_best: Vec::new(),
     };
 
     if signals.is_empty() {
         return result;
     }
 
+    // Expose the self-calibrated speed: the ITU dot unit is 1.2 / WPM seconds.
+    result.estimated_wpm = if timings.dot_duration > 0.0 {
+        1.2 / timings.dot_duration
+    } else {
+        0.0
+    };
+
     // Initialize beam search with default parameters and adaptive gap clustering
     let params = BeamSearchParams::default();
     let mut decoder = BeamSearchDecoder::new(timings, params);
+    if let Some(grammar) = grammar {
+        decoder = decoder.with_grammar(grammar, grammar_penalty);
+    }
 
     let _recognized_patterns = 0;
     let _total_patterns = 0;
@@ -804,31 +1500,22 @@ fn parse_morse_signals_beam_search(
         result.signals_processed += 1;
     }
 
-    // Finalize decoding and get best hypothesis
-    let best_hypothesis = decoder.finalize();
-
-    result.text = best_hypothesis.text;
-
-    // Estimate confidence based on final cost and text length
-    // Lower costs indicate higher confidence, but costs can be negative due to LM bonuses
+    // Finalize decoding, keeping the whole beam so we can report it as a
+    // ranked k-best list alongside the single best decoding.
+    let hypotheses = decoder.finalize_nbest();
+    result.k_best = k_best_posteriors(&hypotheses);
+    result.text = hypotheses
+        .into_iter()
+        .next()
+        .map(|h| h.text)
+        .unwrap_or_default();
+    result.char_confidences = decoder.char_confidences;
+
+    // Whole-message confidence is the geometric mean of the per-character
+    // posteriors, so a single badly garbled letter drags it down much more
+    // than an arithmetic mean would.
     if !result.text.is_empty() {
-        let avg_cost_per_char = best_hypothesis.cost / result.text.len() as f32;
-
-        // Simple linear mapping based on observed cost ranges:
-        // Negative costs (good English): confidence > 0.9
-        // Cost 0-3: confidence 0.85-0.95
-        // Cost 3-8: confidence 0.7-0.85
-        // Cost > 8: confidence < 0.7
-        result.confidence = if avg_cost_per_char < 0.0 {
-            0.95 + (-avg_cost_per_char * 0.005).min(0.05) // Very high confidence for bonuses
-        } else if avg_cost_per_char <= 3.0 {
-            0.95 - avg_cost_per_char * 0.033 // 0.95 to 0.85
-        } else if avg_cost_per_char <= 8.0 {
-            0.85 - (avg_cost_per_char - 3.0) * 0.02 // 0.85 to 0.75
-        } else {
-            0.7 - (avg_cost_per_char - 8.0) * 0.01 // Decrease slowly below 0.7
-        }
-        .max(0.0);
+        result.confidence = geometric_mean(&result.char_confidences);
     }
 
     // For beam search, we don't track individual patterns the same way
@@ -838,12 +1525,26 @@ fn parse_morse_signals_beam_search(
     result
 }
 
+/// Ratio of the spacing unit to the element unit. `1.0` is standard
+/// proportional timing; `>1.0` indicates Farnsworth-style sending, where
+/// characters are keyed at full speed but the gaps between them are
+/// stretched to a slower, separate effective speed (common in training and
+/// practice transmissions).
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct FarnsworthRatio(f32);
+
 /// Detected timing thresholds for morse interpretation using adaptive clustering
 #[derive(Debug, Clone)]
 struct MorseTimings {
     dot_duration: f32,
     /// Clustering-based gap thresholds (discovered from actual signal patterns)
     gap_clusters: GapClusters,
+    /// Spacing unit (`T_space`) estimated independently from the
+    /// inter-character/word gap clusters. Equal to `dot_duration` under
+    /// standard timing; larger under Farnsworth sending.
+    spacing_duration: f32,
+    /// `spacing_duration / dot_duration`.
+    farnsworth_ratio: FarnsworthRatio,
 }
 
 /// Gap classification thresholds discovered through clustering
@@ -884,9 +1585,18 @@ impl MorseTimings {
         // NEW: Cluster OFF durations to find natural gap boundaries
         let gap_clusters = Self::cluster_gap_durations(&off_durations, dot_duration)?;
 
+        // Estimate the spacing unit independently of the element unit, so
+        // Farnsworth-style sending (fast characters, slow spacing) doesn't
+        // get misread as one giant inter-character gap.
+        let spacing_duration =
+            Self::estimate_spacing_duration(&off_durations, dot_duration, &gap_clusters);
+        let farnsworth_ratio = FarnsworthRatio(spacing_duration / dot_duration.max(1e-6));
+
         Ok(Self {
             dot_duration,
             gap_clusters,
+            spacing_duration,
+            farnsworth_ratio,
         })
     }
 
@@ -1019,6 +1729,33 @@ impl MorseTimings {
             inter_to_word_threshold,
         })
     }
+
+    /// Estimate the spacing unit `T_space` from the gaps `gap_clusters`
+    /// already classified as inter-character or word, dividing each back
+    /// down by its ITU ratio (3T, 7T) rather than assuming it scales from
+    /// `dot_duration` the way standard proportional timing does. Falls back
+    /// to `dot_duration` (no detectable Farnsworth stretch) if too few gaps
+    /// fall outside the intra-character cluster to estimate from.
+    fn estimate_spacing_duration(
+        off_durations: &[f32],
+        dot_duration: f32,
+        gap_clusters: &GapClusters,
+    ) -> f32 {
+        let mut candidates = Vec::new();
+        for &duration in off_durations {
+            if duration > gap_clusters.intra_to_inter_threshold {
+                if duration <= gap_clusters.inter_to_word_threshold {
+                    candidates.push(duration / 3.0);
+                } else {
+                    candidates.push(duration / 7.0);
+                }
+            }
+        }
+
+        TimingStats::new(candidates)
+            .map(|stats| stats.median)
+            .unwrap_or(dot_duration)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -1039,19 +1776,252 @@ pub fn morse_interpret(
             confidence: 0.0,
             signals_processed: 0,
             patterns_recognized: 0,
+            estimated_wpm: 0.0,
+            char_confidences: Vec::new(),
+            k_best: Vec::new(),
         });
     }
 
     // Analyze signal timings
-    let timings = MorseTimings::from_signals(signals)?;
+    let mut timings = MorseTimings::from_signals(signals)?;
+
+    // In auto-timing mode, pin the gap thresholds to the ITU dit multiples
+    // (2x dit splits elements/letters, 5x dit splits words), but derive the
+    // word-splitting threshold from the independently estimated spacing
+    // unit rather than the element unit, so Farnsworth-style sending
+    // doesn't read as one giant inter-character gap.
+    if params.auto_timing {
+        timings.gap_clusters = GapClusters {
+            intra_to_inter_threshold: timings.dot_duration * 2.0,
+            inter_to_word_threshold: timings.spacing_duration * 5.0,
+        };
+    }
 
     // Parse signals into text using Phase 3 beam search + language model
-    let result =
-        parse_morse_signals_beam_search(signals, &timings, params.max_output_length as usize);
+    let mut result = parse_morse_signals_beam_search(
+        signals,
+        &timings,
+        params.max_output_length as usize,
+        params.grammar.clone(),
+        params.grammar_penalty,
+    );
+
+    if params.enable_correction && !result.text.is_empty() {
+        let lexicon = crate::correction::Lexicon::common_words();
+        let correction =
+            crate::correction::correct_text(&result.text, 0.0, &lexicon, &params.correction_params);
+        result.text = correction.text;
+    }
 
     Ok(result)
 }
 
+/// Like [`morse_interpret`], but surfaces the top `n` surviving hypotheses
+/// instead of collapsing to a single best guess.
+///
+/// Each [`Decoding`] carries its raw text, beam-search cost, and a
+/// softmax-normalized confidence over the returned set — useful for
+/// disambiguation UIs ("SOS" vs "OSO") or downstream re-ranking.
+pub fn morse_interpret_n_best(
+    signals: &[MorseSignal],
+    params: &MorseInterpretParams,
+    n: usize,
+) -> Result<Vec<Decoding>, String> {
+    if signals.is_empty() || n == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut timings = MorseTimings::from_signals(signals)?;
+    if params.auto_timing {
+        timings.gap_clusters = GapClusters {
+            intra_to_inter_threshold: timings.dot_duration * 2.0,
+            inter_to_word_threshold: timings.spacing_duration * 5.0,
+        };
+    }
+
+    let mut decoder = BeamSearchDecoder::new(&timings, BeamSearchParams::default());
+    if let Some(grammar) = params.grammar.clone() {
+        decoder = decoder.with_grammar(grammar, params.grammar_penalty);
+    }
+    for signal in signals {
+        decoder.update_timing(signal);
+        if signal.on {
+            decoder.process_on_signal(signal);
+        } else {
+            decoder.process_off_signal(signal);
+        }
+
+        if decoder
+            .hypotheses
+            .iter()
+            .any(|h| h.text.len() >= params.max_output_length as usize)
+        {
+            break;
+        }
+    }
+
+    Ok(decoder.best_n(n))
+}
+
+/// Stateful streaming decoder for live keying / live audio demodulation.
+///
+/// Unlike [`morse_interpret`], which consumes the whole signal slice up front,
+/// `MorseDecoder` keeps the beam-search state resident across pushes so a caller
+/// can feed signals one at a time (as they are keyed or demodulated) and render
+/// decoded text as it arrives. Timing is adapted online from each signal.
+pub struct MorseDecoder {
+    inner: BeamSearchDecoder,
+    max_output_length: usize,
+    /// Byte length of text already handed back by [`MorseDecoder::take_text`].
+    returned_len: usize,
+    /// Signals pushed so far, surfaced on [`MorseDecoder::finish`].
+    signals_processed: i32,
+}
+
+impl MorseDecoder {
+    /// Construct a decoder with the given parameters.
+    pub fn new(params: &MorseInterpretParams) -> Self {
+        // Seed timing with a sensible prior; it is refined online as signals
+        // arrive (no full-stream statistics are available up front).
+        const PRIOR_WPM: f32 = 15.0;
+        let dot_duration = 1.2 / PRIOR_WPM;
+        let timings = MorseTimings {
+            dot_duration,
+            gap_clusters: GapClusters {
+                intra_to_inter_threshold: dot_duration * 2.0,
+                inter_to_word_threshold: dot_duration * 5.0,
+            },
+            // No signal history yet to detect a Farnsworth stretch from;
+            // assume standard proportional timing until evidence says otherwise.
+            spacing_duration: dot_duration,
+            farnsworth_ratio: FarnsworthRatio(1.0),
+        };
+
+        let mut inner = BeamSearchDecoder::new(&timings, BeamSearchParams::default());
+        if let Some(grammar) = params.grammar.clone() {
+            inner = inner.with_grammar(grammar, params.grammar_penalty);
+        }
+
+        Self {
+            inner,
+            max_output_length: params.max_output_length.max(0) as usize,
+            returned_len: 0,
+            signals_processed: 0,
+        }
+    }
+
+    /// Feed a single signal and return the current partial decode.
+    ///
+    /// Timing is re-estimated online from every signal (see
+    /// [`BeamSearchDecoder::update_timing`]), so `dot_duration` and the
+    /// gap-cluster thresholds keep up with the sender without needing the
+    /// whole stream up front.
+    pub fn push_signal(&mut self, signal: MorseSignal) -> PartialResult {
+        self.inner.update_timing(&signal);
+        if signal.on {
+            self.inner.process_on_signal(&signal);
+        } else {
+            self.inner.process_off_signal(&signal);
+        }
+        self.signals_processed += 1;
+        self.pending()
+    }
+
+    /// The current best partial decode, including the in-progress character.
+    pub fn pending(&self) -> PartialResult {
+        let best = self.inner.best_hypothesis();
+        let confidence = estimate_confidence(best.cost, best.text.len());
+        let mut text = best.text;
+        if text.len() > self.max_output_length {
+            text.truncate(self.max_output_length);
+        }
+        PartialResult { text, confidence }
+    }
+
+    /// The longest prefix every surviving hypothesis in the beam agrees on.
+    ///
+    /// Unlike [`pending`](Self::pending), which reflects only the current
+    /// best guess and can still be revised by a later signal, a stable
+    /// prefix is shared by the *entire* beam and can be committed
+    /// irrevocably by a live receiver (e.g. appended to a scrollback buffer)
+    /// without risk of it changing on a future character.
+    pub fn stable_prefix(&self) -> String {
+        let mut hypotheses = self.inner.hypotheses.iter();
+        let Some(first) = hypotheses.next() else {
+            return String::new();
+        };
+
+        let mut prefix: Vec<char> = first.text.chars().collect();
+        for hyp in hypotheses {
+            let common = prefix
+                .iter()
+                .zip(hyp.text.chars())
+                .take_while(|(&a, b)| a == *b)
+                .count();
+            prefix.truncate(common);
+            if prefix.is_empty() {
+                break;
+            }
+        }
+        prefix.into_iter().collect()
+    }
+
+    /// Return the part of the current best partial decode
+    /// ([`pending`](Self::pending), including its in-progress character)
+    /// beyond what earlier calls returned, and advance the cursor, so
+    /// successive calls yield only fresh characters. Text already returned
+    /// is not revised if a later signal changes the best hypothesis.
+    pub fn take_text(&mut self) -> String {
+        let text = self.pending().text;
+        if self.returned_len >= text.len() {
+            return String::new();
+        }
+        // Advance to a char boundary to keep multi-byte output valid.
+        let mut start = self.returned_len;
+        while start < text.len() && !text.is_char_boundary(start) {
+            start += 1;
+        }
+        let delta = text[start..].to_string();
+        self.returned_len = text.len();
+        delta
+    }
+
+    /// Consume the decoder, completing any trailing character and returning
+    /// the same full result shape [`morse_interpret`] produces from a batch
+    /// signal slice.
+    pub fn finish(mut self) -> MorseInterpretResult {
+        let hypotheses = self.inner.finalize_nbest();
+        let k_best = k_best_posteriors(&hypotheses);
+        let mut text = hypotheses.into_iter().next().map(|h| h.text).unwrap_or_default();
+        if text.len() > self.max_output_length {
+            text.truncate(self.max_output_length);
+        }
+
+        let char_confidences = self.inner.char_confidences.clone();
+        let confidence = if text.is_empty() {
+            0.0
+        } else {
+            geometric_mean(&char_confidences)
+        };
+        let estimated_wpm = if self.inner.timing_tracker.get_t() > 0.0 {
+            1.2 / self.inner.timing_tracker.get_t()
+        } else {
+            0.0
+        };
+        let patterns_recognized = text.chars().filter(|&c| c != ' ').count() as i32;
+
+        MorseInterpretResult {
+            text,
+            confidence,
+            signals_processed: self.signals_processed,
+            patterns_recognized,
+            estimated_wpm,
+            char_confidences,
+            k_best,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1068,6 +2038,56 @@ mod tests {
         assert_eq!(result.confidence, 0.0);
     }
 
+    #[test]
+    fn test_timing_adapts_to_speed_change() {
+        // Start the tracker slow, then feed a run of fast dots. The tracked dot
+        // unit should shrink and drag the gap thresholds down with it, so the
+        // decoder keeps up with an operator who speeds up mid-message.
+        let timings = MorseTimings {
+            dot_duration: 0.12,
+            gap_clusters: GapClusters {
+                intra_to_inter_threshold: 0.24,
+                inter_to_word_threshold: 0.60,
+            },
+            spacing_duration: 0.12,
+            farnsworth_ratio: FarnsworthRatio(1.0),
+        };
+        let mut decoder = BeamSearchDecoder::new(&timings, BeamSearchParams::default());
+        let before = decoder.timing_tracker.get_t();
+
+        for _ in 0..20 {
+            decoder.update_timing(&create_test_signal(true, 0.04));
+        }
+
+        let after = decoder.timing_tracker.get_t();
+        assert!(after < before, "dot estimate should shrink as sender speeds up");
+        // Gap thresholds re-anchor to the new dot unit (2x / 5x).
+        assert!(
+            (decoder.timing_model.gap_clusters.intra_to_inter_threshold - after * 2.0).abs() < 1e-4
+        );
+    }
+
+    #[test]
+    fn test_estimate_spacing_duration_detects_farnsworth_stretch() {
+        let dot_duration = 0.05;
+        let gap_clusters = GapClusters {
+            intra_to_inter_threshold: dot_duration * 2.0,
+            inter_to_word_threshold: 0.5,
+        };
+        // Farnsworth-style spacing: inter-character gaps (3T_space) and word
+        // gaps (7T_space) both imply a spacing unit of 0.1s, twice the 0.05s
+        // element unit, even though no element ever runs that slow. The word
+        // threshold sits between the 0.3s and 0.7s gap clusters; at 5x the
+        // element unit (0.25s) the 0.3s gaps would count as word gaps.
+        let off_durations = vec![0.3, 0.3, 0.3, 0.7, 0.7];
+        let spacing =
+            MorseTimings::estimate_spacing_duration(&off_durations, dot_duration, &gap_clusters);
+
+        assert!((spacing - 0.1).abs() < 1e-3, "spacing unit was {spacing}");
+        let ratio = spacing / dot_duration;
+        assert!(ratio > 1.5, "expected a detected Farnsworth stretch, got ratio {ratio}");
+    }
+
     #[test]
     fn test_single_dot() {
         let params = MorseInterpretParams::default();
@@ -1135,4 +2155,292 @@ mod tests {
         assert_eq!(result.text, "HELLO");
         assert!(result.confidence > 0.8);
     }
+
+    #[test]
+    fn test_char_confidences_track_emitted_text_and_geometric_mean() {
+        // Clean, unambiguous timing for "E" (.): the beam should agree on
+        // the character with near-certainty.
+        let params = MorseInterpretParams::default();
+        let signals = vec![
+            create_test_signal(true, 0.1),
+            create_test_signal(false, 0.3),
+        ];
+
+        let result = morse_interpret(&signals, &params).unwrap();
+        assert_eq!(result.text, "E");
+        assert_eq!(result.char_confidences.len(), result.text.chars().count());
+        assert!(result.char_confidences[0] > 0.5);
+        assert!((result.confidence - geometric_mean(&result.char_confidences)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_k_best_is_ranked_and_normalized() {
+        let params = MorseInterpretParams::default();
+        // E = .
+        let signals = vec![
+            create_test_signal(true, 0.1),
+            create_test_signal(false, 0.3),
+        ];
+
+        let result = morse_interpret(&signals, &params).unwrap();
+        assert!(!result.k_best.is_empty());
+        assert_eq!(result.k_best[0].0, result.text);
+
+        // Posteriors are sorted descending and sum to a normalized distribution.
+        for pair in result.k_best.windows(2) {
+            assert!(pair[0].1 >= pair[1].1);
+        }
+        let total: f32 = result.k_best.iter().map(|(_, p)| p).sum();
+        assert!((total - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_character_error_rate() {
+        assert_eq!(character_error_rate("SOS", "SOS"), 0.0);
+        assert_eq!(character_error_rate("SOS", "SAS"), 1.0 / 3.0);
+        assert_eq!(character_error_rate("", "AB"), 1.0);
+    }
+
+    #[test]
+    fn test_feature_vector_dot_matches_hypothesis_cost() {
+        // "E" is a single dit: one timing cost, one LM cost, no penalties.
+        let signals = vec![
+            create_test_signal(true, 0.1),
+            create_test_signal(false, 0.3),
+        ];
+        let params = BeamSearchParams::default();
+        let best = decode_nbest_for_training(&signals, &params)
+            .into_iter()
+            .next()
+            .expect("beam should produce at least one hypothesis");
+        assert!((best.features.dot(&params) - best.cost).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_train_returns_finite_weights() {
+        // A tiny "E = ." example: the tuner should run and hand back a set of
+        // finite weights it can decode with.
+        let example = (
+            vec![
+                create_test_signal(true, 0.1),
+                create_test_signal(false, 0.3),
+            ],
+            "E".to_string(),
+        );
+        let tuned = train(&[example], 2, 0.1);
+        for w in tuned.weights() {
+            assert!(w.is_finite());
+        }
+        assert!(tuned.beam_size > 0);
+    }
+
+    #[test]
+    fn test_train_max_step_bounds_weight_change() {
+        // A harder "SOS" example keyed with noticeably late intra-character
+        // gaps, so the oracle/prediction weights should actually diverge and
+        // trigger a MIRA update worth clipping.
+        let mut signals = Vec::new();
+        for _ in 0..3 {
+            signals.push(create_test_signal(true, 0.1));
+            signals.push(create_test_signal(false, 0.19));
+        }
+        let examples = vec![(signals, "SOS".to_string())];
+
+        let default_weights = BeamSearchParams::default().weights();
+        let small_step = train(&examples, 1, 0.001);
+        let large_step = train(&examples, 1, 10.0);
+
+        let total_delta = |tuned: &BeamSearchParams| -> f32 {
+            tuned
+                .weights()
+                .iter()
+                .zip(default_weights)
+                .map(|(w, d)| (w - d).abs())
+                .sum()
+        };
+
+        assert!(total_delta(&small_step) <= total_delta(&large_step) + 1e-6);
+    }
+
+    #[test]
+    fn test_language_model_backs_off_through_orders() {
+        let lm = LanguageModel::new();
+
+        // "SOS" is an explicit trigram override, so the trigram-order query
+        // should return exactly its cost with no backoff penalty applied.
+        let sos_cost = lm.get_cost(b"SO", b'S', 3);
+        assert!((sos_cost - 0.5).abs() < 1e-4);
+
+        // A context/char combo absent from every order falls all the way
+        // back to the flat default cost, picking up one backoff penalty per
+        // order stepped down (here: order 3 -> 2 -> 1 -> 0, so 3 penalties).
+        // '#' never occurs in the generated tables or the overrides.
+        let unseen_cost = lm.get_cost(b"##", b'#', 3);
+        let expected = 3.0 * (-LM_BACKOFF_ALPHA.ln()) + DEFAULT_UNKNOWN_TRIGRAM_COST;
+        assert!((unseen_cost - expected).abs() < 1e-3);
+
+        // Requesting a lower order than the default should use exactly that
+        // order's table (order 1 = unconditional cost of 'Z').
+        let order1_cost = lm.get_cost(b"ZZ", b'Z', 1);
+        assert!(order1_cost <= unseen_cost);
+    }
+
+    #[test]
+    fn test_morse_interpret_n_best() {
+        // S = ... keyed with generous gaps so timing estimation is stable.
+        let mut signals = Vec::new();
+        for _ in 0..3 {
+            signals.push(create_test_signal(true, 0.1));
+            signals.push(create_test_signal(false, 0.1));
+        }
+
+        let params = MorseInterpretParams::default();
+        let decodings = morse_interpret_n_best(&signals, &params, 5).unwrap();
+
+        assert!(!decodings.is_empty());
+        assert!(decodings.len() <= 5);
+        // Costs are sorted ascending (best hypothesis first).
+        for pair in decodings.windows(2) {
+            assert!(pair[0].cost <= pair[1].cost);
+        }
+        // Confidences are a normalized distribution over the returned set.
+        let total_confidence: f32 = decodings.iter().map(|d| d.confidence).sum();
+        assert!((total_confidence - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_grammar_penalizes_decode_that_leaves_accepting_paths() {
+        // E = . — unambiguous single dot.
+        let signals = vec![
+            create_test_signal(true, 0.1),
+            create_test_signal(false, 0.3),
+        ];
+
+        let mut params = MorseInterpretParams::default();
+        let baseline = morse_interpret_n_best(&signals, &params, 1).unwrap();
+        assert_eq!(baseline[0].text, "E");
+
+        // A grammar that only ever accepts "T" can never match "E": the
+        // surviving hypothesis should pick up the grammar penalty as soon as
+        // its one character leaves every accepting path, raising its cost
+        // above the ungated baseline even though the trie still prefers "E".
+        params.grammar = Some(Grammar::alternation(&["T"]));
+        params.grammar_penalty = 5.0;
+        let gated = morse_interpret_n_best(&signals, &params, 1).unwrap();
+        assert_eq!(gated[0].text, "E");
+        assert!(gated[0].cost > baseline[0].cost);
+    }
+
+    #[test]
+    fn test_grammar_matches_callsign_pattern_without_penalty() {
+        // W1AW = .-- .---- .- .--, keyed with generous, unambiguous gaps.
+        let dot = 0.1;
+        let dash = 0.3;
+        let element_gap = 0.1;
+        let char_gap = 0.3;
+
+        let signals = vec![
+            // W = .--
+            create_test_signal(true, dot),
+            create_test_signal(false, element_gap),
+            create_test_signal(true, dash),
+            create_test_signal(false, element_gap),
+            create_test_signal(true, dash),
+            create_test_signal(false, char_gap),
+            // 1 = .----
+            create_test_signal(true, dot),
+            create_test_signal(false, element_gap),
+            create_test_signal(true, dash),
+            create_test_signal(false, element_gap),
+            create_test_signal(true, dash),
+            create_test_signal(false, element_gap),
+            create_test_signal(true, dash),
+            create_test_signal(false, element_gap),
+            create_test_signal(true, dash),
+            create_test_signal(false, char_gap),
+            // A = .-
+            create_test_signal(true, dot),
+            create_test_signal(false, element_gap),
+            create_test_signal(true, dash),
+            create_test_signal(false, char_gap),
+            // W = .--
+            create_test_signal(true, dot),
+            create_test_signal(false, element_gap),
+            create_test_signal(true, dash),
+            create_test_signal(false, element_gap),
+            create_test_signal(true, dash),
+            create_test_signal(false, char_gap),
+        ];
+
+        let mut params = MorseInterpretParams::default();
+        params.grammar = Some(Grammar::from_groups(&[
+            GrammarGroup::new(CharClass::range('A', 'Z').union(CharClass::range('0', '9')), 1, 2),
+            GrammarGroup::new(CharClass::range('0', '9'), 1, 1),
+            GrammarGroup::new(CharClass::range('A', 'Z'), 1, 3),
+        ]));
+        params.grammar_penalty = 5.0;
+
+        let result = morse_interpret(&signals, &params).unwrap();
+        assert_eq!(result.text, "W1AW");
+    }
+
+    #[test]
+    fn test_streaming_decoder() {
+        let params = MorseInterpretParams::default();
+        let mut decoder = MorseDecoder::new(&params);
+
+        // S = ... keyed one signal at a time, then a character gap.
+        for _ in 0..3 {
+            decoder.push_signal(create_test_signal(true, 0.1));
+            decoder.push_signal(create_test_signal(false, 0.1));
+        }
+        decoder.push_signal(create_test_signal(false, 0.3));
+
+        let partial = decoder.pending();
+        assert_eq!(partial.text, "S");
+
+        // take_text returns the finalized characters, then nothing new.
+        assert_eq!(decoder.take_text(), "S");
+        assert_eq!(decoder.take_text(), "");
+    }
+
+    #[test]
+    fn test_stable_prefix_and_finish() {
+        let params = MorseInterpretParams::default();
+        let mut decoder = MorseDecoder::new(&params);
+
+        // S O S, fully keyed with unambiguous gaps.
+        for (on, seconds) in [
+            (true, 0.1),
+            (false, 0.1),
+            (true, 0.1),
+            (false, 0.1),
+            (true, 0.1),
+            (false, 0.3),
+            (true, 0.3),
+            (false, 0.1),
+            (true, 0.3),
+            (false, 0.1),
+            (true, 0.3),
+            (false, 0.3),
+            (true, 0.1),
+            (false, 0.1),
+            (true, 0.1),
+            (false, 0.1),
+            (true, 0.1),
+        ] {
+            decoder.push_signal(create_test_signal(on, seconds));
+        }
+
+        // With the beam narrowed to a single surviving reading, the stable
+        // prefix should match the in-progress text exactly.
+        let stable = decoder.stable_prefix();
+        assert_eq!(stable, decoder.pending().text);
+
+        let result = decoder.finish();
+        assert_eq!(result.text, "SOS");
+        assert_eq!(result.signals_processed, 17);
+        assert_eq!(result.char_confidences.len(), 3);
+        assert_eq!(result.k_best.first().map(|(text, _)| text.as_str()), Some("SOS"));
+    }
 }
@@ -2,15 +2,29 @@
 // Rust port of the original C implementation with WebAssembly bindings
 
 pub mod audio;
+pub mod correction;
+pub mod grammar;
 pub mod interpret;
+pub mod music;
 pub mod patterns;
 pub mod timing;
 pub mod types;
 
 // Re-export main public API
-pub use audio::{morse_audio, morse_audio_size};
-pub use interpret::morse_interpret;
-pub use timing::{morse_timing, morse_timing_size};
+pub use audio::{
+    demodulate_to_signals, detect_morse_signals, detect_tone_frequency, encode_audio, morse_audio,
+    morse_audio_size,
+};
+pub use correction::{correct_text, Correction, CorrectionParams, Lexicon};
+pub use grammar::{CharClass, Grammar, GrammarGroup, GrammarState};
+pub use interpret::{
+    BeamSearchParams, MorseDecoder, morse_interpret, morse_interpret_n_best, train,
+};
+pub use music::{generate_midi, generate_notes};
+pub use patterns::{
+    get_morse_pattern_char, morse_decode, morse_encode, Charset, MorseDictionary,
+};
+pub use timing::{interpret_morse_signals, morse_midi, morse_timing, morse_timing_size};
 pub use types::*;
 
 // Public API for direct Rust usage
@@ -37,6 +51,17 @@ pub fn generate_morse_from_elements(
     audio::morse_audio(elements, audio_params)
 }
 
+/// Render text straight to musical note events (dot/dash -> note, gap ->
+/// rest); see [`music::generate_notes`] for the pitch/velocity mapping.
+pub fn generate_morse_music(
+    text: &str,
+    timing_params: &MorseTimingParams,
+    music_params: &MorseMusicParams,
+) -> Result<Vec<NoteEvent>, String> {
+    let elements = timing::morse_timing(text, timing_params)?;
+    Ok(music::generate_notes(&elements, music_params))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -121,6 +146,105 @@ mod tests {
         assert!(normal_gaps > 0);
     }
 
+    #[test]
+    fn test_international_charset_encodes_accented_letters() {
+        // É = ..-.. — a multi-byte UTF-8 character, not representable in the
+        // single-byte fast table, so this exercises the char-based encoding
+        // path end to end.
+        let params = MorseTimingParams::default();
+        let result = generate_morse_timing("É", &params).unwrap();
+        assert_eq!(
+            result
+                .iter()
+                .filter(|e| e.element_type != MorseElementType::Gap)
+                .map(|e| e.element_type)
+                .collect::<Vec<_>>(),
+            vec![
+                MorseElementType::Dot,
+                MorseElementType::Dot,
+                MorseElementType::Dash,
+                MorseElementType::Dot,
+                MorseElementType::Dot,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ascii_charset_rejects_accented_letters() {
+        use crate::patterns::{Charset, UnknownCharPolicy};
+
+        let params = MorseTimingParams {
+            charset: Charset::Ascii,
+            unknown_char_policy: UnknownCharPolicy::Error,
+            ..Default::default()
+        };
+        assert!(generate_morse_timing("É", &params).is_err());
+    }
+
+    #[test]
+    fn test_decode_pattern_round_trips_every_ascii_pattern() {
+        use crate::patterns::{decode_pattern, get_morse_pattern};
+
+        // Every byte with a known forward pattern should decode back to its
+        // own (uppercased) character via the reverse table.
+        for ch in 0u8..=255u8 {
+            if let Some(pattern) = get_morse_pattern(ch) {
+                let decoded = decode_pattern(pattern)
+                    .unwrap_or_else(|| panic!("no reverse entry for {:?}", ch as char));
+                assert_eq!(decoded, (ch as char).to_ascii_uppercase());
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_pattern_rejects_unknown_sequences() {
+        use crate::patterns::decode_pattern;
+        use crate::types::MorseElementType::{Dash, Dot};
+
+        // ......- (six dots then a dash) isn't any known pattern.
+        assert_eq!(decode_pattern(&[Dot, Dot, Dot, Dot, Dot, Dot, Dash]), None);
+    }
+
+    #[test]
+    fn test_morse_encode_decode_round_trip() {
+        use crate::patterns::{morse_decode, morse_encode, MorseDictionary};
+
+        let dict = MorseDictionary::itu();
+        for text in ["SOS", "HELLO WORLD", "CQ DE W1AW", "123 456"] {
+            let code = morse_encode(text, &dict);
+            assert_eq!(morse_decode(&code), text);
+        }
+    }
+
+    #[test]
+    fn test_morse_encode_decode_international_charset() {
+        use crate::patterns::{morse_decode, morse_encode, MorseDictionary};
+
+        let dict = MorseDictionary::itu();
+        let text = "ÀÄÇÈÉÑÖÜ";
+        let code = morse_encode(text, &dict);
+        assert_eq!(morse_decode(&code), text);
+    }
+
+    #[test]
+    fn test_morse_encode_drops_unknown_characters() {
+        use crate::patterns::{morse_encode, MorseDictionary};
+
+        // '€' has no dictionary entry, so it's silently dropped rather than
+        // emitted as some placeholder pattern.
+        let dict = MorseDictionary::itu();
+        assert_eq!(morse_encode("A€B", &dict), morse_encode("AB", &dict));
+    }
+
+    #[test]
+    fn test_morse_decode_rejects_unknown_pattern() {
+        use crate::patterns::morse_decode;
+
+        // "......." isn't any known letter pattern, so it decodes to '?'
+        // rather than being silently dropped.
+        assert_eq!(morse_decode("......."), "?");
+    }
+
     #[test]
     fn test_morse_interpret() {
         use crate::interpret::morse_interpret;
@@ -261,6 +385,48 @@ mod tests {
         assert!(result.confidence > 0.8);
     }
 
+    #[test]
+    fn test_morse_interpret_correction_pass() {
+        use crate::interpret::morse_interpret;
+        use crate::types::{MorseInterpretParams, MorseTimingParams};
+
+        // "HELLP" isn't a word, but is one morse-weighted substitution away
+        // from "HELLO" (the only 5-letter "HELL"-prefixed entry in the
+        // built-in lexicon), so the correction pass should snap it back.
+        let timing_params = MorseTimingParams::default();
+        let elements = generate_morse_timing("HELLP", &timing_params).unwrap();
+        let signals = timing_elements_to_signals(&elements);
+
+        let uncorrected = morse_interpret(&signals, &MorseInterpretParams::default()).unwrap();
+        assert_eq!(uncorrected.text, "HELLP");
+
+        let params = MorseInterpretParams {
+            enable_correction: true,
+            ..Default::default()
+        };
+        let corrected = morse_interpret(&signals, &params).unwrap();
+        assert_eq!(corrected.text, "HELLO");
+    }
+
+    #[test]
+    fn test_morse_interpret_k_best_is_bounded_and_consistent() {
+        use crate::interpret::morse_interpret;
+        use crate::types::{MorseInterpretParams, MorseTimingParams};
+
+        let timing_params = MorseTimingParams::default();
+        let elements = generate_morse_timing("SOS", &timing_params).unwrap();
+        let signals = timing_elements_to_signals(&elements);
+
+        let result = morse_interpret(&signals, &MorseInterpretParams::default()).unwrap();
+
+        // `k_best` is a bounded list, not the whole surviving beam.
+        assert!(result.k_best.len() <= 10);
+        // With no correction pass, the top `k_best` entry agrees with the
+        // reported `text`.
+        let top = result.k_best.first().map(|(text, _)| text.as_str());
+        assert_eq!(top, Some(result.text.as_str()));
+    }
+
     #[test]
     fn test_round_trip_word() {
         use crate::interpret::morse_interpret;
@@ -383,6 +549,232 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_demodulate_round_trip() {
+        use crate::audio::demodulate_to_signals;
+        use crate::types::DemodParams;
+
+        // Render "E" to audio, then recover on/off signals from the raw samples.
+        let timing_params = MorseTimingParams::default();
+        let audio_params = MorseAudioParams::default();
+        let samples = generate_morse_audio("E", &timing_params, &audio_params).unwrap();
+
+        let demod_params = DemodParams {
+            freq_hz: audio_params.radio_params.freq_hz,
+            ..Default::default()
+        };
+        let signals =
+            demodulate_to_signals(&samples, audio_params.sample_rate as u32, &demod_params);
+
+        // There should be at least one keyed run recovered from the tone.
+        assert!(signals.iter().any(|s| s.on));
+    }
+
+    #[test]
+    fn test_demodulate_fixed_threshold_overrides_auto_tracking() {
+        use crate::audio::demodulate_to_signals;
+        use crate::types::DemodParams;
+
+        let timing_params = MorseTimingParams::default();
+        let audio_params = MorseAudioParams::default();
+        let samples = generate_morse_audio("E", &timing_params, &audio_params).unwrap();
+
+        // An unreachably high fixed threshold should suppress every "on" run,
+        // even though the auto-tracked threshold (a fraction of the running
+        // peak) would otherwise key on the tone.
+        let demod_params = DemodParams {
+            freq_hz: audio_params.radio_params.freq_hz,
+            fixed_threshold: Some(1e6),
+            ..Default::default()
+        };
+        let signals =
+            demodulate_to_signals(&samples, audio_params.sample_rate as u32, &demod_params);
+        assert!(signals.iter().all(|s| !s.on));
+    }
+
+    #[test]
+    fn test_detect_tone_frequency() {
+        use crate::audio::detect_tone_frequency;
+
+        let audio_params = MorseAudioParams::default();
+        let samples =
+            generate_morse_audio("E", &MorseTimingParams::default(), &audio_params).unwrap();
+
+        let carrier = audio_params.radio_params.freq_hz;
+        let found = detect_tone_frequency(&samples, audio_params.sample_rate as u32, 200.0, 1200.0);
+        // Should land within one sweep step of the true carrier.
+        assert!((found - carrier).abs() <= 30.0, "found {found}, carrier {carrier}");
+    }
+
+    #[test]
+    fn test_farnsworth_stretches_gaps_not_elements() {
+        use crate::types::MorseElementType;
+
+        // "EE": one dot each, separated by a single inter-character gap.
+        let standard = generate_morse_timing(
+            "EE",
+            &MorseTimingParams {
+                wpm: 20,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let farnsworth = generate_morse_timing(
+            "EE",
+            &MorseTimingParams {
+                wpm: 20,
+                farnsworth_wpm: 5,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let dot = |els: &[crate::types::MorseElement]| {
+            els.iter()
+                .find(|e| e.element_type == MorseElementType::Dot)
+                .unwrap()
+                .duration_seconds
+        };
+        let gap = |els: &[crate::types::MorseElement]| {
+            els.iter()
+                .find(|e| e.element_type == MorseElementType::Gap)
+                .unwrap()
+                .duration_seconds
+        };
+
+        // Elements stay at the fast character speed...
+        assert!((dot(&standard) - dot(&farnsworth)).abs() < 1e-6);
+        // ...while the inter-character gap is stretched.
+        assert!(gap(&farnsworth) > gap(&standard) * 1.5);
+    }
+
+    #[test]
+    fn test_prosign_token_runs_together() {
+        use crate::types::MorseElementType;
+
+        // <AR> is the run-together prosign .-.-. : five keyed elements with
+        // four intra-element gaps and no inter-character gap.
+        let els = generate_morse_timing("<AR>", &MorseTimingParams::default()).unwrap();
+        let keyed = els
+            .iter()
+            .filter(|e| e.element_type != MorseElementType::Gap)
+            .count();
+        assert_eq!(keyed, 5);
+        assert_eq!(els.len(), 9);
+    }
+
+    #[test]
+    fn test_disabling_prosign_markup_treats_brackets_as_literal_chars() {
+        use crate::types::UnknownCharPolicy;
+
+        let params = MorseTimingParams {
+            enable_prosign_markup: false,
+            unknown_char_policy: UnknownCharPolicy::Error,
+            ..Default::default()
+        };
+
+        // With markup disabled, "<AR>" is no longer a run-together prosign;
+        // '<' isn't a mapped character, so encoding should fail outright.
+        assert!(generate_morse_timing("<AR>", &params).is_err());
+    }
+
+    #[test]
+    fn test_encode_wav_header_and_size() {
+        use crate::audio::encode_audio;
+        use crate::types::MorseAudioFormat;
+
+        let samples = [0.0f32, 0.5, -0.5, 1.0];
+        let bytes = encode_audio(&samples, 8000, MorseAudioFormat::Wav).unwrap();
+
+        // RIFF/WAVE container with a PCM `fmt ` chunk and a `data` chunk.
+        assert_eq!(&bytes[0..4], b"RIFF");
+        assert_eq!(&bytes[8..12], b"WAVE");
+        assert_eq!(&bytes[12..16], b"fmt ");
+        assert_eq!(&bytes[36..40], b"data");
+        // 44-byte header plus one 16-bit sample per input sample.
+        assert_eq!(bytes.len(), 44 + samples.len() * 2);
+    }
+
+    #[test]
+    fn test_detect_morse_signals_from_audio() {
+        use crate::audio::detect_morse_signals;
+        use crate::types::{DetectParams, MorseElementType};
+
+        // Render "A" to audio, then recover the keyed/silent run structure.
+        let timing_params = MorseTimingParams::default();
+        let audio_params = MorseAudioParams::default();
+        let samples = generate_morse_audio("A", &timing_params, &audio_params).unwrap();
+
+        let detect_params = DetectParams {
+            freq_hz: audio_params.radio_params.freq_hz,
+            ..Default::default()
+        };
+        let elements =
+            detect_morse_signals(&samples, audio_params.sample_rate, &detect_params);
+
+        // "A" is dit-dah: at least two keyed runs should be detected.
+        let keyed = elements
+            .iter()
+            .filter(|e| e.element_type == MorseElementType::Dot)
+            .count();
+        assert!(keyed >= 2, "expected at least two keyed runs, got {keyed}");
+    }
+
+    #[test]
+    fn test_morse_midi_header_and_track() {
+        use crate::timing::{morse_midi, morse_timing};
+        use crate::types::MorseMidiParams;
+
+        let elements = morse_timing("SOS", &MorseTimingParams::default()).unwrap();
+        let bytes = morse_midi(&elements, &MorseMidiParams::default()).unwrap();
+
+        // Well-formed SMF: MThd header then an MTrk chunk.
+        assert_eq!(&bytes[0..4], b"MThd");
+        assert_eq!(&bytes[8..10], &[0x00, 0x00], "format 0");
+        assert_eq!(&bytes[10..12], &[0x00, 0x01], "one track");
+        assert_eq!(&bytes[14..18], b"MTrk");
+        // Track must end with an end-of-track meta event.
+        assert_eq!(&bytes[bytes.len() - 3..], &[0xff, 0x2f, 0x00]);
+    }
+
+    #[test]
+    fn test_generate_morse_music_produces_one_note_per_dit_and_dah() {
+        use crate::music::generate_midi;
+        use crate::types::{MorseMusicParams, MorseTimingParams};
+
+        // S = ... , O = ---, S = ...: nine keyed elements total.
+        let notes = generate_morse_music(
+            "SOS",
+            &MorseTimingParams::default(),
+            &MorseMusicParams::default(),
+        )
+        .unwrap();
+        assert_eq!(notes.len(), 9);
+        assert!(notes.windows(2).all(|w| w[1].start_seconds > w[0].start_seconds));
+
+        let midi = generate_midi(&notes, &MorseMusicParams::default()).unwrap();
+        assert_eq!(&midi[0..4], b"MThd");
+        assert_eq!(&midi[14..18], b"MTrk");
+    }
+
+    #[test]
+    fn test_dit_dah_mapping_pitch_accents_dashes_with_octave_jump() {
+        use crate::music::generate_notes;
+        use crate::types::{DitDahMapping, MorseMusicParams, MorseTimingParams};
+
+        let elements = generate_morse_timing("A", &MorseTimingParams::default()).unwrap(); // .-
+        let params = MorseMusicParams {
+            cycle_pitch: false,
+            mapping: DitDahMapping::Pitch,
+            ..Default::default()
+        };
+        let notes = generate_notes(&elements, &params);
+
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes[1].pitch, notes[0].pitch + 12);
+        assert_eq!(notes[0].velocity, notes[1].velocity);
+    }
+
     // Tests with fuzzy, humanized signals to test beam search robustness
 
     #[test]
@@ -681,4 +1073,49 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_interpret_morse_signals_kmeans() {
+        use crate::timing::interpret_morse_signals;
+        use crate::types::{KMeansInterpretParams, MorseTimingParams};
+
+        let timing_params = MorseTimingParams::default();
+        let params = KMeansInterpretParams::default();
+
+        let elements = generate_morse_timing("SOS", &timing_params).unwrap();
+        let signals = timing_elements_to_signals(&elements);
+
+        let text = interpret_morse_signals(&signals, &params).unwrap();
+        assert_eq!(text, "SOS");
+    }
+
+    #[test]
+    fn test_interpret_morse_signals_kmeans_degenerate_equal_gaps() {
+        use crate::timing::interpret_morse_signals;
+        use crate::types::{KMeansInterpretParams, MorseSignal};
+
+        // "HI" (.... ..), but every OFF duration (intra-character, letter, and
+        // word gaps alike) is the same 0.2s value. kmeans_1d's k=3 OFF
+        // clustering then has a single data point repeated, so min == max and
+        // all three centroids initialize to the same value; every gap ties to
+        // centroid 0 ("intra-character"), so no letter boundary is ever
+        // emitted and the dots run together as "......" instead of ".... ..".
+        // This documents that known limitation rather than asserting correct
+        // decoding — a real fix would need kmeans_1d to handle degenerate
+        // (single distinct value) OFF distributions explicitly.
+        let dot = 0.1;
+        let gap = 0.2;
+        let signals: Vec<MorseSignal> = [
+            true, false, true, false, true, false, true, false, true, false, true,
+        ]
+        .iter()
+        .map(|&on| MorseSignal {
+            on,
+            seconds: if on { dot } else { gap },
+        })
+        .collect();
+
+        let text = interpret_morse_signals(&signals, &KMeansInterpretParams::default()).unwrap();
+        assert_ne!(text, "HI");
+    }
 }
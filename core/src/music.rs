@@ -0,0 +1,117 @@
+//! Render Morse timing as musical note events, for users who key off the
+//! dot/dash rhythm for melodic material rather than for legible Morse.
+//!
+//! [`generate_notes`] turns the [`MorseElement`]s from [`crate::timing::morse_timing`]
+//! into a flat list of [`NoteEvent`]s (rests fall out naturally as the gaps
+//! between notes); [`generate_midi`] renders that same note list as a
+//! Standard MIDI File (type 0), reusing the VLQ encoding from
+//! [`crate::timing`].
+
+use crate::timing::write_vlq;
+use crate::types::{DitDahMapping, MorseElement, MorseElementType, MorseMusicParams, NoteEvent};
+
+/// Convert timing elements into note events: each dot/dash becomes one note,
+/// pitched by cycling through `params.scale` from `params.root_note` (or held
+/// at the root when `params.cycle_pitch` is off) and accented per
+/// `params.mapping`; gaps simply advance the clock (a rest).
+pub fn generate_notes(elements: &[MorseElement], params: &MorseMusicParams) -> Vec<NoteEvent> {
+    let intervals = params.scale.intervals();
+    let mut notes = Vec::with_capacity(elements.len());
+    let mut t = 0.0f32;
+    let mut degree: usize = 0;
+
+    for element in elements {
+        if element.element_type == MorseElementType::Gap {
+            t += element.duration_seconds;
+            continue;
+        }
+        let is_dash = element.element_type == MorseElementType::Dash;
+
+        let interval = if params.cycle_pitch {
+            let interval = intervals[degree % intervals.len()];
+            degree += 1;
+            interval
+        } else {
+            intervals[0]
+        };
+
+        let mut pitch = params.root_note.saturating_add(interval);
+        let mut velocity = params.velocity;
+        match params.mapping {
+            DitDahMapping::Pitch if is_dash => pitch = pitch.saturating_add(12),
+            DitDahMapping::Velocity if is_dash => velocity = params.accent_velocity,
+            _ => {}
+        }
+
+        notes.push(NoteEvent {
+            start_seconds: t,
+            duration_seconds: element.duration_seconds,
+            pitch: pitch.min(127),
+            velocity: velocity.min(127),
+        });
+
+        t += element.duration_seconds;
+    }
+
+    notes
+}
+
+/// Render note events into a Standard MIDI File (type 0).
+///
+/// A single tempo meta event (derived from `params.bpm`) opens the track;
+/// each note becomes a note-on/note-off pair, with VLQ-encoded delta ticks
+/// filling the silence in between.
+pub fn generate_midi(notes: &[NoteEvent], params: &MorseMusicParams) -> Result<Vec<u8>, String> {
+    if params.ppq == 0 {
+        return Err("PPQ must be greater than zero".to_string());
+    }
+    if params.bpm <= 0.0 {
+        return Err("BPM must be greater than zero".to_string());
+    }
+
+    let tempo_us_per_quarter = (60_000_000.0 / params.bpm).round() as u32;
+    let seconds_per_quarter = tempo_us_per_quarter as f32 / 1_000_000.0;
+    let ticks_per_second = params.ppq as f32 / seconds_per_quarter;
+    let to_ticks = |seconds: f32| (seconds * ticks_per_second).round().max(0.0) as u32;
+
+    let mut track = Vec::new();
+
+    // Tempo meta event at time zero (0xff 0x51 0x03 <24-bit us/quarter>).
+    write_vlq(&mut track, 0);
+    track.extend_from_slice(&[0xff, 0x51, 0x03]);
+    track.extend_from_slice(&tempo_us_per_quarter.to_be_bytes()[1..]);
+
+    let mut cursor_ticks: u32 = 0;
+    for note in notes {
+        let start_ticks = to_ticks(note.start_seconds);
+        let end_ticks = start_ticks + to_ticks(note.duration_seconds).max(1);
+        let pitch = note.pitch & 0x7f;
+        let velocity = note.velocity & 0x7f;
+
+        write_vlq(&mut track, start_ticks.saturating_sub(cursor_ticks));
+        track.extend_from_slice(&[0x90, pitch, velocity]);
+        cursor_ticks = start_ticks;
+
+        write_vlq(&mut track, end_ticks.saturating_sub(cursor_ticks));
+        track.extend_from_slice(&[0x80, pitch, 0]);
+        cursor_ticks = end_ticks;
+    }
+
+    // End-of-track meta event.
+    write_vlq(&mut track, 0);
+    track.extend_from_slice(&[0xff, 0x2f, 0x00]);
+
+    let mut bytes = Vec::with_capacity(14 + 8 + track.len());
+    // MThd: format 0, one track, division = PPQ.
+    bytes.extend_from_slice(b"MThd");
+    bytes.extend_from_slice(&6u32.to_be_bytes());
+    bytes.extend_from_slice(&0u16.to_be_bytes());
+    bytes.extend_from_slice(&1u16.to_be_bytes());
+    bytes.extend_from_slice(&params.ppq.to_be_bytes());
+    // MTrk: length-prefixed event stream.
+    bytes.extend_from_slice(b"MTrk");
+    bytes.extend_from_slice(&(track.len() as u32).to_be_bytes());
+    bytes.extend_from_slice(&track);
+
+    Ok(bytes)
+}
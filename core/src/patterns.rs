@@ -166,3 +166,340 @@ static MORSE_PATTERNS: [Option<MorsePattern>; 256] = {
 pub fn get_morse_pattern(ch: u8) -> Option<MorsePattern> {
     MORSE_PATTERNS[ch as usize]
 }
+
+/// Pack a pattern into a single integer: two bits per element (`0b01` = dot,
+/// `0b10` = dash) accumulated from the first element, per multimon-ng's
+/// compact Morse encoding. Since no element code is `0b00`, the first
+/// element always occupies the top non-zero bit pair, so patterns of
+/// different lengths can never collide on the packed value alone.
+fn pack_pattern(pattern: &[MorseElementType]) -> u64 {
+    pattern.iter().fold(0u64, |packed, &element| {
+        let bits: u64 = match element {
+            MorseElementType::Dot => 0b01,
+            MorseElementType::Dash => 0b10,
+            MorseElementType::Gap => 0b00, // not expected to appear in a pattern
+        };
+        (packed << 2) | bits
+    })
+}
+
+/// Reverse lookup table for [`decode_pattern`]: every `MORSE_PATTERNS` entry
+/// (one per unique pattern; case variants and other characters sharing a
+/// pattern are folded to a single canonical char), packed via
+/// [`pack_pattern`] and sorted ascending so `decode_pattern` can binary
+/// search it.
+static REVERSE_PATTERNS: &[(u64, u8, char)] = &[
+    (1, PATTERN_E.len() as u8, 'E'),
+    (2, PATTERN_T.len() as u8, 'T'),
+    (5, PATTERN_I.len() as u8, 'I'),
+    (6, PATTERN_A.len() as u8, 'A'),
+    (9, PATTERN_N.len() as u8, 'N'),
+    (10, PATTERN_M.len() as u8, 'M'),
+    (21, PATTERN_S.len() as u8, 'S'),
+    (22, PATTERN_U.len() as u8, 'U'),
+    (25, PATTERN_R.len() as u8, 'R'),
+    (26, PATTERN_W.len() as u8, 'W'),
+    (37, PATTERN_D.len() as u8, 'D'),
+    (38, PATTERN_K.len() as u8, 'K'),
+    (41, PATTERN_G.len() as u8, 'G'),
+    (42, PATTERN_O.len() as u8, 'O'),
+    (85, PATTERN_H.len() as u8, 'H'),
+    (86, PATTERN_V.len() as u8, 'V'),
+    (89, PATTERN_F.len() as u8, 'F'),
+    (101, PATTERN_L.len() as u8, 'L'),
+    (105, PATTERN_P.len() as u8, 'P'),
+    (106, PATTERN_J.len() as u8, 'J'),
+    (149, PATTERN_B.len() as u8, 'B'),
+    (150, PATTERN_X.len() as u8, 'X'),
+    (153, PATTERN_C.len() as u8, 'C'),
+    (154, PATTERN_Y.len() as u8, 'Y'),
+    (165, PATTERN_Z.len() as u8, 'Z'),
+    (166, PATTERN_Q.len() as u8, 'Q'),
+    (341, PATTERN_5.len() as u8, '5'),
+    (342, PATTERN_4.len() as u8, '4'),
+    (346, PATTERN_3.len() as u8, '3'),
+    (362, PATTERN_2.len() as u8, '2'),
+    (405, PATTERN_AMPERSAND.len() as u8, '&'),
+    (409, PATTERN_PLUS.len() as u8, '+'),
+    (426, PATTERN_1.len() as u8, '1'),
+    (597, PATTERN_6.len() as u8, '6'),
+    (598, PATTERN_EQUALS.len() as u8, '='),
+    (601, PATTERN_SLASH.len() as u8, '/'),
+    (617, PATTERN_LPAREN.len() as u8, '('),
+    (661, PATTERN_7.len() as u8, '7'),
+    (677, PATTERN_8.len() as u8, '8'),
+    (681, PATTERN_9.len() as u8, '9'),
+    (682, PATTERN_0.len() as u8, '0'),
+    (1445, PATTERN_QUESTION.len() as u8, '?'),
+    (1446, PATTERN_UNDERSCORE.len() as u8, '_'),
+    (1625, PATTERN_DQUOTE.len() as u8, '"'),
+    (1638, PATTERN_PERIOD.len() as u8, '.'),
+    (1689, PATTERN_AT.len() as u8, '@'),
+    (1705, PATTERN_QUOTE.len() as u8, '\''),
+    (2390, PATTERN_HYPHEN.len() as u8, '-'),
+    (2457, PATTERN_SEMICOLON.len() as u8, ';'),
+    (2458, PATTERN_EXCLAIM.len() as u8, '!'),
+    (2470, PATTERN_RPAREN.len() as u8, ')'),
+    (2650, PATTERN_COMMA.len() as u8, ','),
+    (2709, PATTERN_COLON.len() as u8, ':'),
+    (5526, PATTERN_DOLLAR.len() as u8, '$'),
+];
+
+/// Decode a received element sequence back to a character by packing it the
+/// same way as [`REVERSE_PATTERNS`] and binary-searching the reverse table:
+/// an O(log n) exact-match alternative to walking `&'static [MorseElementType]`
+/// forward patterns one at a time. `None` if the sequence matches no known
+/// pattern (e.g. a beam-search hypothesis that never reached a terminal).
+pub fn decode_pattern(elements: &[MorseElementType]) -> Option<char> {
+    let packed = pack_pattern(elements);
+    let len = elements.len() as u8;
+    REVERSE_PATTERNS
+        .binary_search_by(|&(key, key_len, _)| (key, key_len).cmp(&(packed, len)))
+        .ok()
+        .map(|i| REVERSE_PATTERNS[i].2)
+}
+
+// Secondary table for accented/international letters that don't fit in a
+// single ASCII byte, sorted by `char` so `get_morse_pattern_char` can binary
+// search it. Matches the extensions `MorseDictionary::itu()` seeds itself from.
+const INTERNATIONAL_PATTERNS: &[(char, MorsePattern)] = &[
+    ('À', &[DOT, DASH, DASH, DOT, DASH]), // also Å
+    ('Ä', &[DOT, DASH, DOT, DASH]),       // also Æ
+    ('Ç', &[DASH, DOT, DASH, DOT, DOT]),
+    ('È', &[DOT, DASH, DOT, DOT, DASH]),
+    ('É', &[DOT, DOT, DASH, DOT, DOT]),
+    ('Ñ', &[DASH, DASH, DOT, DASH, DASH]),
+    ('Ö', &[DASH, DASH, DASH, DOT]), // also Ø
+    ('Ü', &[DOT, DOT, DASH, DASH]),
+];
+
+/// Which character set [`get_morse_pattern_char`] may resolve against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Charset {
+    /// Strict ITU core alphabet: ASCII letters, digits, and punctuation only.
+    Ascii,
+    /// [`Charset::Ascii`] plus the common European accented-letter extensions.
+    International,
+}
+
+impl Default for Charset {
+    fn default() -> Self {
+        Self::International
+    }
+}
+
+/// Get a morse pattern for any Unicode character, gated by `charset`.
+///
+/// Falls through to the fast ASCII byte table ([`get_morse_pattern`]) for
+/// ASCII input; for non-ASCII input under [`Charset::International`], also
+/// consults [`INTERNATIONAL_PATTERNS`] (case-insensitively), the small sorted
+/// table of accented letters (À, É, Ñ, Ö, Ü, Ç, ...) common to European CW.
+pub fn get_morse_pattern_char(ch: char, charset: Charset) -> Option<MorsePattern> {
+    if ch.is_ascii() {
+        return get_morse_pattern(ch as u8);
+    }
+    if charset == Charset::Ascii {
+        return None;
+    }
+    let upper = ch.to_uppercase().next().unwrap_or(ch);
+    INTERNATIONAL_PATTERNS
+        .binary_search_by_key(&upper, |&(c, _)| c)
+        .ok()
+        .map(|i| INTERNATIONAL_PATTERNS[i].1)
+}
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// What to do with characters that have no pattern in the active dictionary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UnknownCharPolicy {
+    /// Silently drop the character (historical behavior).
+    Skip,
+    /// Abort encoding with an error.
+    Error,
+    /// Emit a configurable placeholder pattern (the "error" prosign by default).
+    Placeholder,
+}
+
+impl Default for UnknownCharPolicy {
+    fn default() -> Self {
+        Self::Skip
+    }
+}
+
+/// The Morse "error" prosign (eight dits) used as the default placeholder.
+pub const ERROR_PROSIGN: &[MorseElementType] = &[DOT, DOT, DOT, DOT, DOT, DOT, DOT, DOT];
+
+/// A configurable character-to-pattern table for encoding and decoding.
+///
+/// The built-in table covers ITU international extensions (accented letters) on
+/// top of the base ASCII table; callers can extend it with their own entries
+/// and prosigns via [`MorseDictionary::insert`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MorseDictionary {
+    entries: HashMap<char, Vec<MorseElementType>>,
+    /// Named run-together prosigns, keyed by their uppercase name (e.g. `AR`).
+    /// Referenced in input text with angle-bracket tokens like `<AR>`.
+    prosigns: HashMap<String, Vec<MorseElementType>>,
+}
+
+impl MorseDictionary {
+    /// An empty dictionary with no entries.
+    pub fn empty() -> Self {
+        Self {
+            entries: HashMap::new(),
+            prosigns: HashMap::new(),
+        }
+    }
+
+    /// The ITU international table: ASCII letters/digits/punctuation plus the
+    /// common accented extensions used in European CW.
+    pub fn itu() -> Self {
+        let mut dict = Self::empty();
+
+        // Seed from the fast ASCII byte table.
+        for ch in 0u8..=127u8 {
+            if let Some(pattern) = get_morse_pattern(ch) {
+                dict.entries.insert(ch as char, pattern.to_vec());
+            }
+        }
+
+        // ITU / standard international extensions (accented letters).
+        for &(ch, pattern) in INTERNATIONAL_PATTERNS {
+            dict.insert(ch, pattern);
+        }
+
+        // Run-together prosigns, referenced as <AR>, <SK>, ... in input text.
+        dict.insert_prosign("AR", &[DOT, DASH, DOT, DASH, DOT]); // end of message
+        dict.insert_prosign("SK", &[DOT, DOT, DOT, DASH, DOT, DASH]); // end of contact
+        dict.insert_prosign("BT", &[DASH, DOT, DOT, DOT, DASH]); // new paragraph
+        dict.insert_prosign("KN", &[DASH, DOT, DASH, DASH, DOT]); // go ahead, named station
+        dict.insert_prosign("AS", &[DOT, DASH, DOT, DOT, DOT]); // wait
+        dict.insert_prosign("CT", &[DASH, DOT, DASH, DOT, DASH]); // start of message
+        dict.insert_prosign("SOS", &[DOT, DOT, DOT, DASH, DASH, DASH, DOT, DOT, DOT]);
+
+        dict
+    }
+
+    /// Insert or override a character's pattern.
+    pub fn insert(&mut self, ch: char, pattern: &[MorseElementType]) -> &mut Self {
+        self.entries.insert(ch, pattern.to_vec());
+        self
+    }
+
+    /// Look up a character's pattern.
+    pub fn get(&self, ch: char) -> Option<&[MorseElementType]> {
+        self.entries.get(&ch).map(|v| v.as_slice())
+    }
+
+    /// Insert or override a named run-together prosign (name is case-folded).
+    pub fn insert_prosign(&mut self, name: &str, pattern: &[MorseElementType]) -> &mut Self {
+        self.prosigns.insert(name.to_uppercase(), pattern.to_vec());
+        self
+    }
+
+    /// Look up a named prosign's run-together pattern.
+    pub fn get_prosign(&self, name: &str) -> Option<&[MorseElementType]> {
+        self.prosigns.get(&name.to_uppercase()).map(|v| v.as_slice())
+    }
+
+    /// Reverse lookup: find the character whose pattern matches exactly.
+    pub fn decode(&self, pattern: &[MorseElementType]) -> Option<char> {
+        self.entries
+            .iter()
+            .find(|(_, p)| p.as_slice() == pattern)
+            .map(|(&ch, _)| ch)
+    }
+
+    /// Reverse lookup for a run-together prosign, returning its name.
+    pub fn decode_prosign(&self, pattern: &[MorseElementType]) -> Option<&str> {
+        self.prosigns
+            .iter()
+            .find(|(_, p)| p.as_slice() == pattern)
+            .map(|(name, _)| name.as_str())
+    }
+}
+
+impl Default for MorseDictionary {
+    fn default() -> Self {
+        Self::itu()
+    }
+}
+
+/// Render a pattern as a dot-dash string (e.g. `.-`).
+fn pattern_to_string(pattern: &[MorseElementType]) -> String {
+    pattern
+        .iter()
+        .map(|e| match e {
+            MorseElementType::Dash => '-',
+            _ => '.',
+        })
+        .collect()
+}
+
+/// Parse a dot-dash string into a pattern (unknown symbols are ignored).
+fn string_to_pattern(code: &str) -> Vec<MorseElementType> {
+    code.chars()
+        .filter_map(|c| match c {
+            '.' => Some(DOT),
+            '-' => Some(DASH),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Encode text to the canonical dot-dash string form.
+///
+/// Letters are joined with spaces and words with `/`, sharing the same pattern
+/// lookup the timing module uses so the two stay in lock-step. Characters
+/// missing from the dictionary are dropped.
+pub fn morse_encode(text: &str, dict: &MorseDictionary) -> String {
+    let words: Vec<String> = text
+        .split(' ')
+        .map(|word| {
+            word.chars()
+                .filter_map(|ch| {
+                    dict.get(ch)
+                        .or_else(|| ch.to_uppercase().find_map(|c| dict.get(c)))
+                        .map(pattern_to_string)
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect();
+
+    words.join(" / ")
+}
+
+/// Decode a canonical dot-dash string back to text.
+///
+/// Reuses the built-in ITU dictionary for reverse lookup; letter groups that
+/// don't correspond to a known pattern are emitted as `?`.
+pub fn morse_decode(code: &str) -> String {
+    let dict = MorseDictionary::itu();
+    let mut out = String::new();
+
+    for (w, word) in code.split('/').enumerate() {
+        if w > 0 {
+            out.push(' ');
+        }
+        for letter in word.split_whitespace() {
+            let pattern = string_to_pattern(letter);
+            if pattern.is_empty() {
+                continue;
+            }
+            // The table holds both cases of each ASCII letter under one
+            // pattern; report the uppercase one.
+            out.push(
+                dict.decode(&pattern)
+                    .map(|c| c.to_ascii_uppercase())
+                    .unwrap_or('?'),
+            );
+        }
+    }
+
+    out
+}
@@ -1,5 +1,10 @@
-use crate::patterns::get_morse_pattern;
-use crate::types::{MorseElement, MorseElementType, MorseTimingParams};
+use crate::patterns::{
+    get_morse_pattern_char, morse_decode, MorseDictionary, UnknownCharPolicy, ERROR_PROSIGN,
+};
+use crate::types::{
+    KMeansInterpretParams, MorseElement, MorseElementType, MorseMidiParams, MorseSignal,
+    MorseTimingParams,
+};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 // ITU timing constants
@@ -79,13 +84,32 @@ pub fn morse_timing(text: &str, params: &MorseTimingParams) -> Result<Vec<MorseE
     };
 
     let dot_sec = DOT_LENGTH_WPM / params.wpm as f32;
+
+    // Farnsworth timing: key elements at `wpm` but stretch inter-character and
+    // inter-word gaps to hit the slower `farnsworth_wpm`. The ARRL delay unit
+    // `td = (60c - 37.2s) / (19cs)` reduces to the standard dot unit when
+    // `s == c`, so the character gap is `3·td` and the word gap `7·td`.
+    let (char_gap_sec, word_gap_sec) = if params.farnsworth_wpm > 0
+        && params.farnsworth_wpm < params.wpm
+    {
+        let c = params.wpm as f32;
+        let s = params.farnsworth_wpm as f32;
+        let td = (60.0 * c - 37.2 * s) / (19.0 * c * s);
+        (DOTS_PER_CHAR_GAP as f32 * td, DOTS_PER_WORD_GAP as f32 * td)
+    } else {
+        (
+            dot_sec * DOTS_PER_CHAR_GAP as f32,
+            dot_sec * DOTS_PER_WORD_GAP as f32,
+        )
+    };
+
     let mut elements = Vec::new();
-    let mut chars = text.bytes().peekable();
+    let mut chars = text.chars().peekable();
 
     while let Some(ch) = chars.next() {
         // Handle spaces as inter-word gaps
-        if ch == b' ' {
-            let word_gap_duration = dot_sec * DOTS_PER_WORD_GAP as f32 * params.word_gap_multiplier;
+        if ch == ' ' {
+            let word_gap_duration = word_gap_sec * params.word_gap_multiplier;
             let duration =
                 apply_humanization(word_gap_duration, params.humanization_factor, &mut rng);
 
@@ -96,13 +120,81 @@ pub fn morse_timing(text: &str, params: &MorseTimingParams) -> Result<Vec<MorseE
             continue;
         }
 
+        // Handle named run-together prosigns in angle brackets, e.g. <AR>.
+        if ch == '<' && params.enable_prosign_markup {
+            let mut name = String::new();
+            for next in chars.by_ref() {
+                if next == '>' {
+                    break;
+                }
+                name.push(next);
+            }
+
+            // Resolve against the active dictionary (caller-supplied or ITU).
+            let resolved = match &params.dictionary {
+                Some(dict) => dict.get_prosign(&name).map(|p| p.to_vec()),
+                None => MorseDictionary::itu().get_prosign(&name).map(|p| p.to_vec()),
+            };
+
+            let pattern = match resolved {
+                Some(p) => p,
+                None => match params.unknown_char_policy {
+                    UnknownCharPolicy::Skip => continue,
+                    UnknownCharPolicy::Error => {
+                        return Err(format!("Unknown prosign: <{}>", name));
+                    }
+                    UnknownCharPolicy::Placeholder => ERROR_PROSIGN.to_vec(),
+                },
+            };
+
+            // Inter-character gap before the prosign if it isn't the first token.
+            if !elements.is_empty()
+                && elements
+                    .last()
+                    .map(|e| e.element_type != MorseElementType::Gap)
+                    .unwrap_or(true)
+            {
+                let duration =
+                    apply_humanization(char_gap_sec, params.humanization_factor, &mut rng);
+                elements.push(MorseElement {
+                    element_type: MorseElementType::Gap,
+                    duration_seconds: duration,
+                });
+            }
+
+            // Keyed as a single run-together character (1-dot intra gaps only).
+            for (i, &element_type) in pattern.iter().enumerate() {
+                let base_duration = match element_type {
+                    MorseElementType::Dot => dot_sec,
+                    MorseElementType::Dash => dot_sec * DOTS_PER_DASH as f32,
+                    MorseElementType::Gap => dot_sec,
+                };
+                let duration =
+                    apply_humanization(base_duration, params.humanization_factor, &mut rng);
+                elements.push(MorseElement {
+                    element_type,
+                    duration_seconds: duration,
+                });
+
+                if i < pattern.len() - 1 {
+                    let gap_duration =
+                        apply_humanization(dot_sec, params.humanization_factor, &mut rng);
+                    elements.push(MorseElement {
+                        element_type: MorseElementType::Gap,
+                        duration_seconds: gap_duration,
+                    });
+                }
+            }
+            continue;
+        }
+
         // Handle prosigns in brackets [...]
-        if ch == b'[' {
+        if ch == '[' {
             let mut prosign_char_count = 0;
 
             // Process characters inside brackets (skip spaces and invalid chars)
             while let Some(&prosign_ch) = chars.peek() {
-                if prosign_ch == b']' {
+                if prosign_ch == ']' {
                     chars.next(); // consume the closing bracket
                     break;
                 }
@@ -110,11 +202,11 @@ pub fn morse_timing(text: &str, params: &MorseTimingParams) -> Result<Vec<MorseE
                 let prosign_ch = chars.next().unwrap();
 
                 // Skip spaces inside prosigns
-                if prosign_ch == b' ' {
+                if prosign_ch == ' ' {
                     continue;
                 }
 
-                if let Some(pattern) = get_morse_pattern(prosign_ch) {
+                if let Some(pattern) = get_morse_pattern_char(prosign_ch, params.charset) {
                     // Add 1-dot gap between characters in prosign (except for first character)
                     if prosign_char_count > 0 {
                         let duration =
@@ -154,8 +246,25 @@ pub fn morse_timing(text: &str, params: &MorseTimingParams) -> Result<Vec<MorseE
                 }
             }
         } else {
-            // Handle regular character
-            if let Some(pattern) = get_morse_pattern(ch) {
+            // Handle regular character. Consult the caller-supplied dictionary
+            // first, falling back to the built-in (charset-gated) table.
+            let resolved = match &params.dictionary {
+                Some(dict) => dict.get(ch).map(|p| p.to_vec()),
+                None => get_morse_pattern_char(ch, params.charset).map(|p| p.to_vec()),
+            };
+
+            let pattern: Vec<MorseElementType> = match resolved {
+                Some(p) => p,
+                None => match params.unknown_char_policy {
+                    UnknownCharPolicy::Skip => continue,
+                    UnknownCharPolicy::Error => {
+                        return Err(format!("Unknown character: {:?}", ch));
+                    }
+                    UnknownCharPolicy::Placeholder => ERROR_PROSIGN.to_vec(),
+                },
+            };
+
+            {
                 // Add inter-character gap if not the first character
                 if !elements.is_empty() {
                     // Check if last element was not already a gap to avoid duplicate gaps
@@ -165,7 +274,7 @@ pub fn morse_timing(text: &str, params: &MorseTimingParams) -> Result<Vec<MorseE
                         .unwrap_or(true);
 
                     if should_add_gap {
-                        let inter_char_duration = dot_sec * DOTS_PER_CHAR_GAP as f32;
+                        let inter_char_duration = char_gap_sec;
                         let duration = apply_humanization(
                             inter_char_duration,
                             params.humanization_factor,
@@ -210,6 +319,210 @@ pub fn morse_timing(text: &str, params: &MorseTimingParams) -> Result<Vec<MorseE
     Ok(elements)
 }
 
+/// Run 1-D k-means over `data`, returning the centroids sorted ascending.
+///
+/// Centroids are initialized spread evenly across the min..max range. Empty
+/// clusters keep their previous centroid so fewer distinct values than `k`
+/// collapses gracefully rather than producing NaNs.
+fn kmeans_1d(data: &[f32], k: usize, max_iter: i32, convergence: f32) -> Vec<f32> {
+    if data.is_empty() || k == 0 {
+        return Vec::new();
+    }
+
+    let min = data.iter().cloned().fold(f32::INFINITY, f32::min);
+    let max = data.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+
+    // Initialize centroids spread across the range.
+    let mut centroids: Vec<f32> = (0..k)
+        .map(|i| {
+            if k == 1 {
+                (min + max) / 2.0
+            } else {
+                min + (max - min) * i as f32 / (k - 1) as f32
+            }
+        })
+        .collect();
+
+    for _ in 0..max_iter.max(1) {
+        let mut sums = vec![0.0f32; k];
+        let mut counts = vec![0usize; k];
+
+        for &x in data {
+            let idx = nearest_centroid(&centroids, x);
+            sums[idx] += x;
+            counts[idx] += 1;
+        }
+
+        let mut max_shift = 0.0f32;
+        for i in 0..k {
+            if counts[i] > 0 {
+                let new_c = sums[i] / counts[i] as f32;
+                max_shift = max_shift.max((new_c - centroids[i]).abs());
+                centroids[i] = new_c;
+            }
+        }
+
+        if max_shift < convergence {
+            break;
+        }
+    }
+
+    centroids.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    centroids
+}
+
+/// Index of the centroid nearest to `x`.
+fn nearest_centroid(centroids: &[f32], x: f32) -> usize {
+    centroids
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| {
+            (x - **a)
+                .abs()
+                .partial_cmp(&(x - **b).abs())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+/// Interpret alternating ON/OFF signal durations into text using 1-D k-means
+/// duration clustering (k=2 over ON durations for dot/dash, k=3 over OFF
+/// durations for element/letter/word gaps), then a standard Morse lookup.
+pub fn interpret_morse_signals(
+    signals: &[MorseSignal],
+    params: &KMeansInterpretParams,
+) -> Result<String, String> {
+    // Drop noise-short signals up front.
+    let filtered: Vec<&MorseSignal> = signals
+        .iter()
+        .filter(|s| s.seconds >= params.noise_threshold)
+        .collect();
+
+    let on_durations: Vec<f32> = filtered.iter().filter(|s| s.on).map(|s| s.seconds).collect();
+    let off_durations: Vec<f32> =
+        filtered.iter().filter(|s| !s.on).map(|s| s.seconds).collect();
+
+    if on_durations.is_empty() {
+        // All silence (or empty) decodes to nothing.
+        return Ok(String::new());
+    }
+
+    let on_centroids = kmeans_1d(
+        &on_durations,
+        2,
+        params.max_k_means_iterations,
+        params.convergence_threshold,
+    );
+    let off_centroids = kmeans_1d(
+        &off_durations,
+        3,
+        params.max_k_means_iterations,
+        params.convergence_threshold,
+    );
+
+    // Reassemble a Morse string: smallest ON centroid -> dot, larger -> dash;
+    // the three OFF centroids -> intra-element (nothing), letter gap, word gap.
+    let mut code = String::new();
+    for s in &filtered {
+        if s.on {
+            let idx = nearest_centroid(&on_centroids, s.seconds);
+            code.push(if idx == 0 { '.' } else { '-' });
+        } else {
+            match nearest_centroid(&off_centroids, s.seconds) {
+                0 => {}                        // intra-character gap
+                1 => code.push(' '),           // letter boundary
+                _ => code.push_str(" / "),     // word boundary
+            }
+        }
+    }
+
+    let mut text = morse_decode(&code);
+    let max_len = params.max_output_length.max(0) as usize;
+    if text.chars().count() > max_len {
+        text = text.chars().take(max_len).collect();
+    }
+
+    Ok(text)
+}
+
+/// Encode a value as a MIDI variable-length quantity (big-endian, 7 bits per
+/// byte with the high bit set on all but the last byte).
+pub(crate) fn write_vlq(out: &mut Vec<u8>, mut value: u32) {
+    let mut buffer = [0u8; 5];
+    let mut i = buffer.len();
+    i -= 1;
+    buffer[i] = (value & 0x7f) as u8;
+    value >>= 7;
+    while value > 0 {
+        i -= 1;
+        buffer[i] = ((value & 0x7f) as u8) | 0x80;
+        value >>= 7;
+    }
+    out.extend_from_slice(&buffer[i..]);
+}
+
+/// Render Morse timing into a Standard MIDI File (type 0).
+///
+/// Each non-gap element becomes a note-on at the configured pitch/velocity
+/// followed by a note-off after its duration; gaps advance the delta-tick
+/// counter with no note. Durations are converted to ticks using the params'
+/// PPQ and tempo. Returns the raw bytes of a single-track SMF: an `MThd`
+/// header, one `MTrk` chunk with VLQ-encoded delta times, and a terminating
+/// end-of-track meta event.
+pub fn morse_midi(elements: &[MorseElement], params: &MorseMidiParams) -> Result<Vec<u8>, String> {
+    if params.ppq == 0 {
+        return Err("PPQ must be greater than zero".to_string());
+    }
+    if params.tempo_us_per_quarter == 0 {
+        return Err("Tempo must be greater than zero".to_string());
+    }
+
+    let seconds_per_quarter = params.tempo_us_per_quarter as f32 / 1_000_000.0;
+    let ticks_per_second = params.ppq as f32 / seconds_per_quarter;
+    let to_ticks = |seconds: f32| (seconds * ticks_per_second).round().max(0.0) as u32;
+
+    let pitch = params.pitch & 0x7f;
+    let velocity = params.velocity & 0x7f;
+
+    // Build the track event stream. `pending` accumulates gap/silent ticks that
+    // become the delta time of the next emitted event.
+    let mut track = Vec::new();
+    let mut pending: u32 = 0;
+    for element in elements {
+        let ticks = to_ticks(element.duration_seconds);
+        match element.element_type {
+            MorseElementType::Gap => pending += ticks,
+            MorseElementType::Dot | MorseElementType::Dash => {
+                // Note-on at the accumulated delta, note-off after the duration.
+                write_vlq(&mut track, pending);
+                track.extend_from_slice(&[0x90, pitch, velocity]);
+                write_vlq(&mut track, ticks);
+                track.extend_from_slice(&[0x80, pitch, 0]);
+                pending = 0;
+            }
+        }
+    }
+
+    // End-of-track meta event (delta carries any trailing gap).
+    write_vlq(&mut track, pending);
+    track.extend_from_slice(&[0xff, 0x2f, 0x00]);
+
+    let mut bytes = Vec::with_capacity(14 + 8 + track.len());
+    // MThd: format 0, one track, division = PPQ.
+    bytes.extend_from_slice(b"MThd");
+    bytes.extend_from_slice(&6u32.to_be_bytes());
+    bytes.extend_from_slice(&0u16.to_be_bytes());
+    bytes.extend_from_slice(&1u16.to_be_bytes());
+    bytes.extend_from_slice(&params.ppq.to_be_bytes());
+    // MTrk: length-prefixed event stream.
+    bytes.extend_from_slice(b"MTrk");
+    bytes.extend_from_slice(&(track.len() as u32).to_be_bytes());
+    bytes.extend_from_slice(&track);
+
+    Ok(bytes)
+}
+
 /// Calculate size needed for timing elements (without actually generating them)
 pub fn morse_timing_size(text: &str, params: &MorseTimingParams) -> Result<usize, String> {
     // For size calculation, we can just generate the actual elements and count them
@@ -24,6 +24,24 @@ pub enum MorseAudioMode {
     Telegraph = 1,
 }
 
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum MorseAudioFormat {
+    /// Self-contained 16-bit PCM WAV (RIFF). Always available, no dependencies.
+    Wav = 0,
+    /// FLAC lossless (gated behind the `flac` feature).
+    Flac = 1,
+    /// Ogg Vorbis lossy (gated behind the `vorbis` feature).
+    OggVorbis = 2,
+}
+
+impl Default for MorseAudioFormat {
+    fn default() -> Self {
+        Self::Wav
+    }
+}
+
 #[wasm_bindgen]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize_repr, Deserialize_repr)]
 #[repr(u8)]
@@ -41,6 +59,24 @@ pub struct MorseTimingParams {
     pub word_gap_multiplier: f32,
     pub humanization_factor: f32,
     pub random_seed: u32,
+    /// Farnsworth character speed: dits/dahs are keyed at `wpm` while the
+    /// inter-character/word gaps are stretched to this slower effective speed.
+    /// `0` disables Farnsworth (standard proportional timing).
+    pub farnsworth_wpm: i32,
+    /// Optional character table; `None` uses the built-in ITU dictionary.
+    #[serde(skip)]
+    pub dictionary: Option<crate::patterns::MorseDictionary>,
+    /// How to handle characters missing from the active dictionary.
+    pub unknown_char_policy: crate::patterns::UnknownCharPolicy,
+    /// Which built-in character set to resolve against when `dictionary` is
+    /// `None`; ignored otherwise. Defaults to the full international table
+    /// (ASCII plus accented extensions); restrict to [`crate::patterns::Charset::Ascii`]
+    /// for strict ITU core-alphabet-only encoding.
+    pub charset: crate::patterns::Charset,
+    /// Whether `<NAME>`-style angle-bracket tokens (e.g. `<AR>`, `<SOS>`) are
+    /// parsed as run-together prosigns. Defaults to `true`; disable to treat
+    /// `<`/`>` as literal, unmapped characters.
+    pub enable_prosign_markup: bool,
 }
 
 impl Default for MorseTimingParams {
@@ -50,6 +86,190 @@ impl Default for MorseTimingParams {
             word_gap_multiplier: 1.0,
             humanization_factor: 0.0,
             random_seed: 0,
+            farnsworth_wpm: 0,
+            dictionary: None,
+            unknown_char_policy: crate::patterns::UnknownCharPolicy::Skip,
+            charset: crate::patterns::Charset::default(),
+            enable_prosign_markup: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DetectParams {
+    /// Expected tone frequency in Hz (used for autocorrelation confirmation).
+    pub freq_hz: f32,
+    /// RMS analysis window / hop size in milliseconds.
+    pub window_ms: f32,
+    /// Keying threshold as a fraction of the peak RMS envelope.
+    pub threshold_ratio: f32,
+    /// Minimum normalized autocorrelation for a window to count as a tone.
+    pub autocorr_threshold: f32,
+}
+
+impl Default for DetectParams {
+    fn default() -> Self {
+        Self {
+            freq_hz: 440.0,
+            window_ms: 5.0,
+            threshold_ratio: 0.35,
+            autocorr_threshold: 0.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MorseMidiParams {
+    /// Ticks per quarter note written into the MThd header.
+    pub ppq: u16,
+    /// Tempo in microseconds per quarter note (default makes a 20 WPM dot ~a 32nd note).
+    pub tempo_us_per_quarter: u32,
+    /// MIDI note number sounded for each dot/dash (0-127).
+    pub pitch: u8,
+    /// Note-on velocity (1-127).
+    pub velocity: u8,
+}
+
+impl Default for MorseMidiParams {
+    fn default() -> Self {
+        Self {
+            ppq: 480,
+            // 120 BPM: one quarter note = 0.5 s, so a 20 WPM dot (~0.06 s) is a
+            // short, musically sensible note.
+            tempo_us_per_quarter: 500_000,
+            pitch: 69, // A4
+            velocity: 100,
+        }
+    }
+}
+
+/// A musical scale to draw cycling pitches from in [`crate::music::generate_notes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Scale {
+    /// All twelve semitones.
+    Chromatic,
+    /// Major (Ionian) scale: whole/whole/half/whole/whole/whole/half.
+    Major,
+    /// Natural minor (Aeolian) scale.
+    Minor,
+    /// Minor pentatonic: root, minor third, fourth, fifth, minor seventh.
+    MinorPentatonic,
+}
+
+impl Scale {
+    /// Semitone offsets from the root, in ascending order within one octave.
+    pub fn intervals(self) -> &'static [u8] {
+        match self {
+            Scale::Chromatic => &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+            Scale::Major => &[0, 2, 4, 5, 7, 9, 11],
+            Scale::Minor => &[0, 2, 3, 5, 7, 8, 10],
+            Scale::MinorPentatonic => &[0, 3, 5, 7, 10],
+        }
+    }
+}
+
+impl Default for Scale {
+    fn default() -> Self {
+        Self::Major
+    }
+}
+
+/// Which musical dimension carries the dot/dash distinction in
+/// [`crate::music::generate_notes`]; the other dimension is held fixed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DitDahMapping {
+    /// Dashes sound one octave above dots; velocity is fixed at `velocity`.
+    Pitch,
+    /// Dashes sound at `accent_velocity` instead of `velocity`; pitch only
+    /// cycles through the scale.
+    Velocity,
+}
+
+impl Default for DitDahMapping {
+    fn default() -> Self {
+        Self::Velocity
+    }
+}
+
+/// A single musical note rendered from a dot or dash.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteEvent {
+    /// Seconds from the start of the piece.
+    pub start_seconds: f32,
+    pub duration_seconds: f32,
+    /// MIDI note number (0-127).
+    pub pitch: u8,
+    /// Note-on velocity (1-127).
+    pub velocity: u8,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MorseMusicParams {
+    /// Tempo in quarter notes per minute; drives the MIDI tempo meta event
+    /// and tick resolution (note start/duration in [`NoteEvent`] stay in
+    /// real seconds regardless of tempo).
+    pub bpm: f32,
+    /// MIDI note number (0-127) for the scale root.
+    pub root_note: u8,
+    /// Scale each successive note cycles through, starting at the root.
+    pub scale: Scale,
+    /// Whether successive notes advance through `scale` (`true`) or all
+    /// sound at the root (`false`).
+    pub cycle_pitch: bool,
+    /// Whether dot/dash identity is expressed as pitch (octave jump on
+    /// dashes) or velocity (accent on dashes).
+    pub mapping: DitDahMapping,
+    /// Base note-on velocity (1-127) for dots, and for dashes when `mapping`
+    /// is [`DitDahMapping::Pitch`].
+    pub velocity: u8,
+    /// Note-on velocity (1-127) for dashes when `mapping` is
+    /// [`DitDahMapping::Velocity`].
+    pub accent_velocity: u8,
+    /// Ticks per quarter note written into the MIDI header.
+    pub ppq: u16,
+}
+
+impl Default for MorseMusicParams {
+    fn default() -> Self {
+        Self {
+            bpm: 120.0,
+            root_note: 60, // C4
+            scale: Scale::default(),
+            cycle_pitch: true,
+            mapping: DitDahMapping::default(),
+            velocity: 90,
+            accent_velocity: 110,
+            ppq: 480,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct KMeansInterpretParams {
+    /// Maximum k-means iterations before stopping.
+    pub max_k_means_iterations: i32,
+    /// Stop once the largest centroid shift falls below this value.
+    pub convergence_threshold: f32,
+    /// Drop any signal shorter than this (seconds) as noise.
+    pub noise_threshold: f32,
+    /// Cap on decoded output length.
+    pub max_output_length: i32,
+}
+
+impl Default for KMeansInterpretParams {
+    fn default() -> Self {
+        Self {
+            max_k_means_iterations: 100,
+            convergence_threshold: 1e-4,
+            noise_threshold: 0.01,
+            max_output_length: 1000,
         }
     }
 }
@@ -106,6 +326,16 @@ pub struct MorseAudioParams {
     pub low_pass_cutoff: f32,
     pub high_pass_cutoff: f32,
     pub audio_mode: MorseAudioMode,
+    /// Peaking/bell EQ center frequency in Hz.
+    pub bell_freq: f32,
+    /// Peaking/bell EQ quality factor.
+    pub bell_q: f32,
+    /// Peaking/bell EQ gain in dB (0 = bypassed).
+    pub bell_gain_db: f32,
+    /// Container format for [`crate::audio::encode_audio`] to write the
+    /// generated samples into. Ignored by [`crate::audio::morse_audio`]
+    /// itself, which only ever produces raw `f32` samples.
+    pub format: MorseAudioFormat,
     #[serde(flatten)]
     pub radio_params: MorseRadioParams,
     #[serde(flatten)]
@@ -120,6 +350,10 @@ impl Default for MorseAudioParams {
             low_pass_cutoff: 20000.0,
             high_pass_cutoff: 20.0,
             audio_mode: MorseAudioMode::Radio,
+            bell_freq: 1000.0,
+            bell_q: 1.0,
+            bell_gain_db: 0.0,
+            format: MorseAudioFormat::default(),
             radio_params: MorseRadioParams::default(),
             telegraph_params: MorseTelegraphParams::default(),
         }
@@ -133,24 +367,135 @@ pub struct MorseSignal {
     pub seconds: f32,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DemodParams {
+    /// Expected carrier (tone) frequency in Hz
+    pub freq_hz: f32,
+    /// Length of each analysis window in samples
+    pub window_size: usize,
+    /// Hop between successive (overlapping) windows in samples
+    pub hop_size: usize,
+    /// Span of the moving-average magnitude smoother, in windows
+    pub smoothing_window: usize,
+    /// Minimum magnitude to consider the line keyed at all (noise squelch)
+    pub squelch: f32,
+    /// Keying threshold as a fraction of the running peak magnitude
+    pub threshold_ratio: f32,
+    /// Dead time after each edge before another edge may be committed, in seconds
+    pub holdoff_seconds: f32,
+    /// Absolute envelope level to key against instead of auto-tracking a
+    /// fraction of the running peak. `None` (default) auto-detects the
+    /// squelch/threshold as `threshold_ratio` of a decaying peak estimate,
+    /// same as the reference decoder; set this for a fixed-speed recording
+    /// with a known, stable signal level.
+    pub fixed_threshold: Option<f32>,
+}
+
+impl Default for DemodParams {
+    fn default() -> Self {
+        Self {
+            freq_hz: 440.0,
+            window_size: 256,
+            hop_size: 128,
+            smoothing_window: 9,
+            squelch: 0.02,
+            threshold_ratio: 2.0 / 3.0,
+            holdoff_seconds: 0.010,
+            fixed_threshold: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase", default)]
 pub struct MorseInterpretParams {
     pub max_output_length: i32,
+    /// Estimate dit length (and the dit/dah + gap thresholds) directly from the
+    /// signal stream instead of assuming the caller knows the sender's speed.
+    pub auto_timing: bool,
+    /// Optional character table used for reverse lookup; `None` uses the
+    /// built-in ITU dictionary.
+    #[serde(skip)]
+    pub dictionary: Option<crate::patterns::MorseDictionary>,
+    /// Optional finite-state grammar (callsign/Q-code/structured-traffic
+    /// format) biasing or filtering decode hypotheses; `None` decodes free
+    /// text as before.
+    #[serde(skip)]
+    pub grammar: Option<crate::grammar::Grammar>,
+    /// Blend weight for `grammar`: `0.0` disables grammar scoring even if
+    /// one is attached; a large value effectively filters hypotheses that
+    /// leave the grammar's accepting paths.
+    pub grammar_penalty: f32,
+    /// Run the decoded text through [`crate::correction::correct_text`]
+    /// against a common-word lexicon before returning it, snapping
+    /// likely-typo words to their nearest dictionary match. Off by default,
+    /// since it can overcorrect short or deliberately unusual text.
+    pub enable_correction: bool,
+    /// Tuning for the correction pass; only used when `enable_correction` is set.
+    #[serde(skip)]
+    pub correction_params: crate::correction::CorrectionParams,
 }
 
 impl Default for MorseInterpretParams {
     fn default() -> Self {
         Self {
             max_output_length: 1000,
+            auto_timing: true,
+            dictionary: None,
+            grammar: None,
+            grammar_penalty: 5.0,
+            enable_correction: false,
+            correction_params: crate::correction::CorrectionParams::default(),
         }
     }
 }
 
+/// Partial decode emitted by the streaming [`crate::interpret::MorseDecoder`]
+/// after each pushed signal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartialResult {
+    /// Best-hypothesis text decoded so far, including the in-progress character.
+    pub text: String,
+    /// Confidence of the current best hypothesis in [0, 1].
+    pub confidence: f32,
+}
+
+/// One alternate decoding from an N-best list (see
+/// [`crate::interpret::morse_interpret_n_best`]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Decoding {
+    /// Decoded text for this hypothesis.
+    pub text: String,
+    /// Raw accumulated beam-search cost (lower is better).
+    pub cost: f32,
+    /// Confidence in `[0, 1]`, normalized by softmax over the negated costs
+    /// of the hypotheses returned alongside this one.
+    pub confidence: f32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MorseInterpretResult {
     pub text: String,
+    /// Geometric mean of `char_confidences`; `0.0` if `text` is empty.
     pub confidence: f32,
     pub signals_processed: i32,
     pub patterns_recognized: i32,
+    /// Words-per-minute estimated from the signal timing (0.0 if unknown)
+    pub estimated_wpm: f32,
+    /// Posterior confidence of each character in `text`, in order: the
+    /// beam-search mass (softmax over `-cost`) that agreed with the
+    /// consensus decoding at that position when it was emitted, divided by
+    /// the total mass retained at that point. Flags ambiguous characters
+    /// (e.g. a gap sitting near an intra/inter-character threshold) that the
+    /// single rolled-up `confidence` figure would hide.
+    pub char_confidences: Vec<f32>,
+    /// The surviving beam, deduplicated and truncated to a bounded k-best
+    /// list (see `DEFAULT_K_BEST` in `interpret.rs`), ranked descending by
+    /// posterior probability (`exp(-cost) / Σ exp(-cost)` over the retained
+    /// entries). The first entry's text matches `text` unless the correction
+    /// pass (`enable_correction`) changed `text`; k-best entries are never
+    /// corrected. Callers that want alternative decodings (e.g. "SOS" vs
+    /// "OSO") can read past it without re-running the decoder.
+    pub k_best: Vec<(String, f32)>,
 }